@@ -0,0 +1,335 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, SyncSender, TrySendError},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::client_ip::ClientIp;
+
+/// Lines buffered for the writer thread before new lines are dropped
+const ACCESS_LOG_QUEUE: usize = 16_384;
+
+/// When the access log file is rotated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessLogRotation {
+    /// Never rotate; rely on external tooling (e.g. logrotate with copytruncate)
+    Never,
+    /// Rotate at the first write on a later UTC day than the file's (default)
+    #[default]
+    Daily,
+    /// Rotate once the file would exceed `access_log_max_bytes`
+    Size,
+}
+
+/// Open access log file and its rotation bookkeeping
+#[derive(Debug)]
+struct LogFile {
+    file: File,
+    written: u64,
+    day: u64,
+}
+
+/// Synchronous access log file with daily or size-based rotation
+///
+/// Rotated files are renamed next to the active one with a date suffix
+/// (`access.log.2026-10-14`) for daily rotation or a millisecond timestamp
+/// suffix for size rotation. Only the newest `max_files` rotated files are
+/// kept. Each line is a single JSON object.
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    rotation: AccessLogRotation,
+    max_bytes: u64,
+    max_files: usize,
+    state: LogFile,
+}
+
+impl RotatingFile {
+    /// Open (or create) the access log file for appending
+    ///
+    /// # Arguments
+    /// - `path` - Active access log file path (see `AppConfig::access_log_path`)
+    /// - `rotation` - Rotation policy
+    /// - `max_bytes` - File size that triggers `AccessLogRotation::Size`
+    /// - `max_files` - Rotated files to keep; older ones are deleted
+    ///
+    /// # Returns
+    /// - `Ok(RotatingFile)` - File is open for appending
+    /// - `Err(std::io::Error)` - File could not be opened
+    pub fn open(
+        path: impl Into<PathBuf>,
+        rotation: AccessLogRotation,
+        max_bytes: u64,
+        max_files: usize,
+    ) -> std::io::Result<Self> {
+        let path = path.into();
+        let state = open_file(&path)?;
+        Ok(Self {
+            path,
+            rotation,
+            max_bytes,
+            max_files,
+            state,
+        })
+    }
+
+    /// Append one line, rotating first if the policy requires it
+    pub fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        let suffix = match self.rotation {
+            AccessLogRotation::Never => None,
+            AccessLogRotation::Daily => {
+                (current_day() != self.state.day).then(|| format_date(self.state.day))
+            }
+            AccessLogRotation::Size => (self.state.written > 0
+                && self.state.written + line.len() as u64 > self.max_bytes)
+                .then(|| unix_millis().to_string()),
+        };
+
+        if let Some(suffix) = suffix {
+            let mut rotated = self.path.clone().into_os_string();
+            rotated.push(format!(".{suffix}"));
+            std::fs::rename(&self.path, rotated)?;
+            self.state = open_file(&self.path)?;
+            if let Err(err) = self.prune() {
+                tracing::warn!("Failed to prune rotated access logs: {}", err);
+            }
+        }
+
+        self.state.file.write_all(line)?;
+        self.state.written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Delete the oldest rotated files beyond `max_files`
+    ///
+    /// Only files with a suffix this writer produces are considered, so
+    /// unrelated siblings (`access.log.gz`, `gateway.pid`) are never touched.
+    fn prune(&self) -> std::io::Result<()> {
+        let (Some(dir), Some(name)) = (self.path.parent(), self.path.file_name()) else {
+            return Ok(());
+        };
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let prefix = format!("{}.", name.to_string_lossy());
+
+        let mut rotated = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let is_rotated = file_name
+                .to_string_lossy()
+                .strip_prefix(&prefix)
+                .is_some_and(is_rotation_suffix);
+            if !is_rotated {
+                continue;
+            }
+            let modified = entry.metadata()?.modified().unwrap_or(UNIX_EPOCH);
+            rotated.push((modified, entry.file_name(), entry.path()));
+        }
+
+        // Newest first; date and millisecond suffixes break mtime ties
+        rotated.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+        for (_, _, path) in rotated.into_iter().skip(self.max_files) {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check whether `suffix` is one `write_line` produces when rotating: a
+/// `YYYY-MM-DD` date (daily) or a millisecond timestamp (size)
+fn is_rotation_suffix(suffix: &str) -> bool {
+    let bytes = suffix.as_bytes();
+    let is_date = bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+    let is_millis = !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit);
+    is_date || is_millis
+}
+
+/// Non-blocking access log handle
+///
+/// Lines are handed to a dedicated writer thread over a bounded channel, so
+/// request handling never waits on disk I/O. When the queue is full (the
+/// disk can't keep up) new lines are dropped with a warning.
+#[derive(Debug, Clone)]
+pub struct AccessLog {
+    sender: SyncSender<Vec<u8>>,
+}
+
+impl AccessLog {
+    /// Open the access log file and start its writer thread
+    ///
+    /// # Arguments
+    /// See `RotatingFile::open`
+    ///
+    /// # Returns
+    /// - `Ok(AccessLog)` - File is open and the writer thread is running
+    /// - `Err(std::io::Error)` - File could not be opened or the thread spawned
+    pub fn open(
+        path: impl Into<PathBuf>,
+        rotation: AccessLogRotation,
+        max_bytes: u64,
+        max_files: usize,
+    ) -> std::io::Result<Self> {
+        Self::spawn(RotatingFile::open(path, rotation, max_bytes, max_files)?)
+    }
+
+    /// Start a writer thread for an open file; it exits once every handle is dropped
+    pub fn spawn(mut file: RotatingFile) -> std::io::Result<Self> {
+        let (sender, receiver) = mpsc::sync_channel::<Vec<u8>>(ACCESS_LOG_QUEUE);
+        std::thread::Builder::new()
+            .name("access-log".to_string())
+            .spawn(move || {
+                for line in receiver {
+                    if let Err(err) = file.write_line(&line) {
+                        tracing::warn!("Failed to write access log: {}", err);
+                    }
+                }
+            })?;
+        Ok(Self { sender })
+    }
+
+    /// Queue one line for the writer thread without blocking
+    pub fn write_line(&self, line: Vec<u8>) {
+        match self.sender.try_send(line) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                tracing::warn!("Access log queue full; dropping line");
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                tracing::warn!("Access log writer stopped; dropping line");
+            }
+        }
+    }
+}
+
+/// Open the active file in append mode and pick up its size and age
+///
+/// A non-empty file is dated by its last modification, so a file left over
+/// from an earlier day is rotated on the first write rather than appended to.
+fn open_file(path: &Path) -> std::io::Result<LogFile> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let metadata = file.metadata()?;
+    let day = metadata
+        .modified()
+        .ok()
+        .filter(|_| metadata.len() > 0)
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or_else(current_day, |age| age.as_secs() / 86_400);
+    Ok(LogFile {
+        file,
+        written: metadata.len(),
+        day,
+    })
+}
+
+/// Milliseconds since the Unix epoch
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Days since the Unix epoch (UTC)
+fn current_day() -> u64 {
+    unix_millis() / 86_400_000
+}
+
+/// Format days since the Unix epoch as `YYYY-MM-DD` (proleptic Gregorian, UTC)
+fn format_date(days: u64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Format milliseconds since the Unix epoch as an RFC 3339 UTC timestamp
+fn format_timestamp(millis: u64) -> String {
+    let secs_of_day = (millis / 1000) % 86_400;
+    format!(
+        "{}T{:02}:{:02}:{:02}.{:03}Z",
+        format_date(millis / 86_400_000),
+        secs_of_day / 3600,
+        (secs_of_day / 60) % 60,
+        secs_of_day % 60,
+        millis % 1000
+    )
+}
+
+/// Access log middleware that writes one JSON line per completed request
+///
+/// - Must run outside `request_id_middleware` (reads the response header) and
+///   inside `client_ip_middleware` (reads `ClientIp`)
+/// - Lines are written by a background thread; write failures are logged
+///   and never fail the request
+pub async fn access_log_middleware(
+    State(log): State<AccessLog>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let uri = request.uri().to_string();
+    let client_ip = request
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| ip.to_string());
+    let user_agent = request
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let response = next.run(request).await;
+
+    let header_str = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+
+    let entry = json!({
+        "timestamp": format_timestamp(unix_millis()),
+        "request_id": header_str("x-request-id"),
+        "client_ip": client_ip,
+        "method": method,
+        "uri": uri,
+        "status": response.status().as_u16(),
+        "latency_ms": started.elapsed().as_millis() as u64,
+        "response_bytes": header_str("content-length").and_then(|len| len.parse::<u64>().ok()),
+        "user_agent": user_agent,
+    });
+
+    let mut line = entry.to_string().into_bytes();
+    line.push(b'\n');
+    log.write_line(line);
+
+    response
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::error_envelope;
+
+/// Paths never subject to fault injection, so load balancers keep the instance
+const CHAOS_EXEMPT_PATHS: &[&str] = &["/healthz"];
+
+/// Fault injection settings for `chaos_middleware` (only used when `chaos_enabled`)
+#[derive(Debug, Clone, Copy)]
+pub struct Chaos {
+    /// Delay added to sampled requests
+    pub latency: Duration,
+    /// Fraction of requests (0.0-1.0) that get the delay
+    pub latency_probability: f64,
+    /// Fraction of requests (0.0-1.0) answered with 503
+    pub error_probability: f64,
+}
+
+/// Draw a uniform sample in [0, 1) from the v4 UUID random source
+fn random_unit() -> f64 {
+    let (bits, _) = Uuid::new_v4().as_u64_pair();
+    // Keep 53 bits so the value is exactly representable as f64
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Decide whether a fault with the given probability fires for this request
+fn sample(probability: f64) -> bool {
+    probability > 0.0 && random_unit() < probability
+}
+
+/// Chaos middleware that injects latency and 503s into a sample of requests
+///
+/// - Latency is added before the request is handled
+/// - Injected errors use the standard 503 envelope and skip the handler
+/// - Health checks are exempt
+pub async fn chaos_middleware(
+    State(chaos): State<Chaos>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if CHAOS_EXEMPT_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    if sample(chaos.latency_probability) {
+        tracing::debug!(
+            latency_ms = chaos.latency.as_millis() as u64,
+            "chaos: injecting latency"
+        );
+        tokio::time::sleep(chaos.latency).await;
+    }
+
+    if sample(chaos.error_probability) {
+        tracing::debug!("chaos: injecting 503");
+        return error_envelope(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Fault injected by chaos testing",
+        );
+    }
+
+    next.run(request).await
+}
@@ -0,0 +1,110 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    extract::{connect_info::MockConnectInfo, ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+
+/// Header carrying the proxy chain, appended to by each proxy hop
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Proxy networks whose `X-Forwarded-For` entries are trusted
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Arc<Vec<IpNet>>);
+
+impl TrustedProxies {
+    /// Create from the configured `trusted_proxies` list
+    pub fn new(networks: &[IpNet]) -> Self {
+        Self(Arc::new(networks.to_vec()))
+    }
+
+    /// Check whether an address belongs to a trusted proxy
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|net| net.contains(ip))
+    }
+}
+
+/// Client address resolved by `client_ip_middleware`, stored in request extensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Resolve the real client address for a request
+///
+/// `X-Forwarded-For` is only consulted when the direct peer is a trusted
+/// proxy. The chain is then walked right-to-left, skipping trusted proxies,
+/// and the first untrusted address is the client. Entries left of it were
+/// supplied by the client and are ignored, so they cannot be spoofed.
+///
+/// # Arguments
+/// - `peer` - Address of the directly connected socket peer
+/// - `headers` - Request headers (all `X-Forwarded-For` fields are read in order)
+/// - `trusted` - Trusted proxy networks
+///
+/// # Returns
+/// - The first untrusted address in the chain, or
+/// - the leftmost address if every hop is trusted, or
+/// - the last trusted hop if the chain contains an unparsable entry
+pub fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted: &TrustedProxies) -> IpAddr {
+    if !trusted.contains(&peer) {
+        return peer;
+    }
+
+    let entries: Vec<&str> = headers
+        .get_all(X_FORWARDED_FOR)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+
+    let mut client = peer;
+    for entry in entries.iter().rev() {
+        let Ok(ip) = entry.parse::<IpAddr>() else {
+            // Garbage in the chain: stop at the last hop we could trust
+            break;
+        };
+
+        client = ip;
+        if !trusted.contains(&ip) {
+            break;
+        }
+    }
+
+    client
+}
+
+/// Client IP middleware that stores the resolved `ClientIp` in request extensions
+///
+/// - Needs the server to be run with `ConnectInfo<SocketAddr>` (or a
+///   `MockConnectInfo` layer in tests); without it no `ClientIp` is inserted
+/// - Handlers and embedder layers can read `Extension<ClientIp>`
+pub async fn client_ip_middleware(
+    State(trusted): State<TrustedProxies>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    // Same lookup as the `ConnectInfo` extractor, which can't be optional
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr)
+        .or_else(|| {
+            request
+                .extensions()
+                .get::<MockConnectInfo<SocketAddr>>()
+                .map(|MockConnectInfo(addr)| *addr)
+        });
+
+    if let Some(peer) = peer {
+        let ip = client_ip(peer.ip(), request.headers(), &trusted);
+        request.extensions_mut().insert(ClientIp(ip));
+    }
+
+    next.run(request).await
+}
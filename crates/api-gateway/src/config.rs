@@ -1,8 +1,11 @@
+use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 use url::Url;
 
+use crate::{access_log::AccessLogRotation, proxy_protocol::ProxyProtocolMode, RequestIdScheme};
+
 /// Application configuration for the API Gateway service.
 ///
 /// Supports hierarchical configuration loading with precedence:
@@ -21,13 +24,144 @@ pub struct AppConfig {
     #[serde(default = "default_timeout_ms")]
     pub request_timeout_ms: u64,
 
-    /// Upstream service mappings (service_name -> URL)
-    #[serde(default = "default_upstreams")]
-    pub upstreams: HashMap<String, String>,
+    /// Timeout for clients to finish sending a buffered request body in milliseconds (1-300000)
+    #[serde(default = "default_client_body_timeout_ms")]
+    pub client_body_timeout_ms: u64,
+
+    /// Disable Nagle's algorithm (TCP_NODELAY) on accepted connections
+    #[serde(default = "default_tcp_nodelay")]
+    pub tcp_nodelay: bool,
+
+    /// Listener socket receive buffer (SO_RCVBUF) in bytes (4096-67108864, default: OS)
+    #[serde(default)]
+    pub socket_recv_buffer_bytes: Option<usize>,
+
+    /// Listener socket send buffer (SO_SNDBUF) in bytes (4096-67108864, default: OS)
+    #[serde(default)]
+    pub socket_send_buffer_bytes: Option<usize>,
+
+    /// Maximum number of open client connections (default: unlimited)
+    #[serde(default)]
+    pub max_connections: Option<usize>,
+
+    /// PROXY protocol (v1/v2) on accepted connections: "off" (default), "optional" or "required"
+    #[serde(default)]
+    pub proxy_protocol: ProxyProtocolMode,
+
+    /// Upstream service mappings (service_name -> URL), parsed during validation
+    ///
+    /// Serializes back to strings in `Url`'s normalized form, so a bare origin
+    /// such as `http://host:3003` round-trips as `http://host:3003/`
+    #[serde(default)]
+    pub upstreams: HashMap<String, Url>,
+
+    /// Maximum number of upstream services allowed in configuration
+    #[serde(default = "default_max_upstreams")]
+    pub max_upstreams: usize,
 
     /// Allowed CORS origins (use ["*"] for all)
     #[serde(default = "default_cors_origins")]
     pub cors_origins: Vec<String>,
+
+    /// Send `Access-Control-Allow-Credentials: true` (specific origins only)
+    #[serde(default)]
+    pub cors_allow_credentials: bool,
+
+    /// Maximum length of the request path and query (requests above get 414)
+    #[serde(default = "default_max_uri_length")]
+    pub max_uri_length: usize,
+
+    /// Maximum number of request header fields (requests above get 431)
+    #[serde(default = "default_max_header_count")]
+    pub max_header_count: usize,
+
+    /// Maximum combined size of request header names and values in bytes
+    #[serde(default = "default_max_header_bytes")]
+    pub max_header_bytes: usize,
+
+    /// Maximum declared request body size in bytes (requests above get 413)
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    /// Reject requests whose body length differs from Content-Length with 400
+    #[serde(default)]
+    pub strict_content_length: bool,
+
+    /// Host assumed for HTTP/1.0 requests without a Host header (default: reject with 400)
+    #[serde(default)]
+    pub http10_default_host: Option<String>,
+
+    /// Scheme for generated request IDs: "uuid_v4" (default), "uuid_v7" or "ulid"
+    #[serde(default)]
+    pub request_id_scheme: RequestIdScheme,
+
+    /// Proxy networks (CIDRs or bare IPs) trusted to set X-Forwarded-For
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpNet>,
+
+    /// Request paths that skip info-level request logging (errors still log)
+    #[serde(default = "default_log_exclude_paths")]
+    pub log_exclude_paths: Vec<String>,
+
+    /// File receiving JSON access log lines (default: no access log)
+    #[serde(default)]
+    pub access_log_path: Option<String>,
+
+    /// Access log rotation: "daily" (default), "size" or "never"
+    #[serde(default)]
+    pub access_log_rotation: AccessLogRotation,
+
+    /// Access log size that triggers "size" rotation in bytes
+    #[serde(default = "default_access_log_max_bytes")]
+    pub access_log_max_bytes: u64,
+
+    /// Rotated access log files to keep; older ones are deleted
+    #[serde(default = "default_access_log_max_files")]
+    pub access_log_max_files: usize,
+
+    /// Emit startup information as a single JSON log event instead of pretty lines
+    #[serde(default)]
+    pub startup_summary_json: bool,
+
+    /// Static HTML error pages (status code -> file path) for browser clients
+    #[serde(default)]
+    pub error_pages: HashMap<u16, String>,
+
+    /// Answer `/favicon.ico` locally instead of returning 404
+    #[serde(default)]
+    pub serve_favicon: bool,
+
+    /// Icon file served for `/favicon.ico` (default: empty 204 response)
+    #[serde(default)]
+    pub favicon_path: Option<String>,
+
+    /// Master switch for fault injection; chaos settings are ignored unless set
+    #[serde(default)]
+    pub chaos_enabled: bool,
+
+    /// Delay injected into sampled requests in milliseconds (0-300000)
+    #[serde(default)]
+    pub chaos_latency_ms: u64,
+
+    /// Fraction of requests (0.0-1.0) that get `chaos_latency_ms` added
+    #[serde(default)]
+    pub chaos_latency_probability: f64,
+
+    /// Fraction of requests (0.0-1.0) answered with an injected 503
+    #[serde(default)]
+    pub chaos_error_probability: f64,
+
+    /// Config service URL fetched at startup (TOML or JSON), below env vars in precedence
+    #[serde(default)]
+    pub remote_config_url: Option<String>,
+
+    /// Fail startup when the remote config cannot be fetched (default: fall back to local)
+    #[serde(default)]
+    pub remote_config_required: bool,
+
+    /// Timeout for each remote config fetch attempt in milliseconds (1-300000)
+    #[serde(default = "default_remote_config_timeout_ms")]
+    pub remote_config_timeout_ms: u64,
 }
 
 /// Raw configuration for deserialization before validation
@@ -39,10 +173,74 @@ pub struct AppConfigRaw {
     pub port: u16,
     #[serde(default = "default_timeout_ms")]
     pub request_timeout_ms: u64,
+    #[serde(default = "default_client_body_timeout_ms")]
+    pub client_body_timeout_ms: u64,
+    #[serde(default = "default_tcp_nodelay")]
+    pub tcp_nodelay: bool,
+    #[serde(default)]
+    pub socket_recv_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    pub socket_send_buffer_bytes: Option<usize>,
+    #[serde(default)]
+    pub max_connections: Option<usize>,
+    #[serde(default)]
+    pub proxy_protocol: ProxyProtocolMode,
     #[serde(default = "default_upstreams")]
     pub upstreams: HashMap<String, String>,
+    #[serde(default = "default_max_upstreams")]
+    pub max_upstreams: usize,
     #[serde(default = "default_cors_origins")]
     pub cors_origins: Vec<String>,
+    #[serde(default)]
+    pub cors_allow_credentials: bool,
+    #[serde(default = "default_max_uri_length")]
+    pub max_uri_length: usize,
+    #[serde(default = "default_max_header_count")]
+    pub max_header_count: usize,
+    #[serde(default = "default_max_header_bytes")]
+    pub max_header_bytes: usize,
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    #[serde(default)]
+    pub strict_content_length: bool,
+    #[serde(default)]
+    pub http10_default_host: Option<String>,
+    #[serde(default)]
+    pub request_id_scheme: RequestIdScheme,
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    #[serde(default = "default_log_exclude_paths")]
+    pub log_exclude_paths: Vec<String>,
+    #[serde(default)]
+    pub access_log_path: Option<String>,
+    #[serde(default)]
+    pub access_log_rotation: AccessLogRotation,
+    #[serde(default = "default_access_log_max_bytes")]
+    pub access_log_max_bytes: u64,
+    #[serde(default = "default_access_log_max_files")]
+    pub access_log_max_files: usize,
+    #[serde(default)]
+    pub startup_summary_json: bool,
+    #[serde(default = "default_error_pages")]
+    pub error_pages: HashMap<String, String>,
+    #[serde(default)]
+    pub serve_favicon: bool,
+    #[serde(default)]
+    pub favicon_path: Option<String>,
+    #[serde(default)]
+    pub chaos_enabled: bool,
+    #[serde(default)]
+    pub chaos_latency_ms: u64,
+    #[serde(default)]
+    pub chaos_latency_probability: f64,
+    #[serde(default)]
+    pub chaos_error_probability: f64,
+    #[serde(default)]
+    pub remote_config_url: Option<String>,
+    #[serde(default)]
+    pub remote_config_required: bool,
+    #[serde(default = "default_remote_config_timeout_ms")]
+    pub remote_config_timeout_ms: u64,
 }
 
 /// Configuration-related errors
@@ -68,15 +266,78 @@ pub enum ConfigError {
     #[error("Invalid upstream URL for service '{0}': {1}")]
     InvalidUpstreamUrl(String, String),
 
+    /// Socket buffer size validation error (setting name, value)
+    #[error("Invalid {0}: {1}. Must be between 4096 and 67108864 bytes")]
+    InvalidSocketBuffer(String, usize),
+
+    /// Connection limit validation error
+    #[error("Invalid max_connections: {0}. Must be greater than 0")]
+    InvalidMaxConnections(usize),
+
+    /// Too many upstream services configured (count, limit)
+    #[error("Too many upstream services: {0} configured, maximum is {1} (see max_upstreams)")]
+    TooManyUpstreams(usize, usize),
+
     /// CORS origin validation error
     #[error("Invalid CORS origin: {0}")]
     InvalidCorsOrigin(String),
+
+    /// URI length limit validation error
+    #[error("Invalid max_uri_length: {0}. Must be greater than 0")]
+    InvalidMaxUriLength(usize),
+
+    /// Body size limit validation error
+    #[error("Invalid max_body_bytes: {0}. Must be greater than 0")]
+    InvalidMaxBodyBytes(usize),
+
+    /// Header limit validation error (setting name)
+    #[error("Invalid {0}: must be greater than 0")]
+    InvalidHeaderLimit(String),
+
+    /// HTTP/1.0 default host validation error
+    #[error("Invalid http10_default_host: {0}. Must be a host name with optional port")]
+    InvalidHttp10DefaultHost(String),
+
+    /// Trusted proxy CIDR validation error
+    #[error("Invalid trusted proxy: {0}. Must be a CIDR (e.g. 10.0.0.0/8) or IP address")]
+    InvalidTrustedProxy(String),
+
+    /// Log exclude path validation error
+    #[error("Invalid log exclude path: {0}. Must start with '/'")]
+    InvalidLogExcludePath(String),
+
+    /// Chaos testing setting validation error
+    #[error("Invalid chaos config: {0}")]
+    InvalidChaos(String),
+
+    /// Remote config setting validation error
+    #[error("Invalid remote config: {0}")]
+    InvalidRemoteConfig(String),
+
+    /// Remote config could not be fetched and `remote_config_required` is set (URL, reason)
+    #[error("Failed to fetch required remote config from '{0}': {1}")]
+    RemoteConfigFetch(String, String),
+
+    /// Access log setting validation error
+    #[error("Invalid access log config: {0}")]
+    InvalidAccessLog(String),
+
+    /// Error page validation error (status code, reason)
+    #[error("Invalid error page for status '{0}': {1}")]
+    InvalidErrorPage(String, String),
+
+    /// Favicon file validation error
+    #[error("Invalid favicon_path: file not found: {0}")]
+    InvalidFaviconPath(String),
 }
 
 // ============================================================================
 // Default Values
 // ============================================================================
 
+/// Config files searched by `AppConfig::load` (extension is inferred)
+const DEFAULT_CONFIG_PATHS: &[&str] = &["config", "../../config"];
+
 fn default_host() -> String {
     "127.0.0.1".into()
 }
@@ -89,14 +350,62 @@ fn default_timeout_ms() -> u64 {
     15000
 }
 
+fn default_client_body_timeout_ms() -> u64 {
+    10000
+}
+
+fn default_tcp_nodelay() -> bool {
+    true
+}
+
 fn default_upstreams() -> HashMap<String, String> {
     HashMap::new()
 }
 
+fn default_max_upstreams() -> usize {
+    1000
+}
+
 fn default_cors_origins() -> Vec<String> {
     vec!["*".to_string()]
 }
 
+fn default_max_uri_length() -> usize {
+    8 * 1024
+}
+
+fn default_max_header_count() -> usize {
+    100
+}
+
+fn default_max_header_bytes() -> usize {
+    32 * 1024
+}
+
+fn default_max_body_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_log_exclude_paths() -> Vec<String> {
+    vec!["/healthz".to_string()]
+}
+
+fn default_access_log_max_bytes() -> u64 {
+    100 * 1024 * 1024
+}
+
+fn default_access_log_max_files() -> usize {
+    7
+}
+
+fn default_remote_config_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_error_pages() -> HashMap<String, String> {
+    HashMap::new()
+}
+
 // ============================================================================
 // Configuration Loading
 // ============================================================================
@@ -110,16 +419,7 @@ impl AppConfig {
     pub fn load() -> Result<Self, ConfigError> {
         let _ = dotenvy::dotenv();
 
-        let cfg = ::config::Config::builder()
-            .set_default("host", default_host())?
-            .set_default("port", default_port())?
-            .set_default("request_timeout_ms", default_timeout_ms())?
-            .set_default("upstreams", default_upstreams())?
-            .set_default("cors_origins", default_cors_origins())?
-            .add_source(::config::File::with_name("config").required(false))
-            .add_source(::config::File::with_name("../../config").required(false))
-            .add_source(::config::Environment::with_prefix("APP").separator("_"))
-            .build()?;
+        let cfg = Self::builder(DEFAULT_CONFIG_PATHS, None)?.build()?;
 
         let raw_config: AppConfigRaw = cfg.try_deserialize()?;
         Self::validate_and_convert(raw_config)
@@ -136,18 +436,118 @@ impl AppConfig {
     pub fn load_from_file(config_path: &str) -> Result<Self, ConfigError> {
         let _ = dotenvy::dotenv();
 
-        let cfg = ::config::Config::builder()
+        let cfg = Self::builder(&[config_path], None)?.build()?;
+
+        let raw_config: AppConfigRaw = cfg.try_deserialize()?;
+        Self::validate_and_convert(raw_config)
+    }
+
+    /// Load configuration including an optional remote config service
+    ///
+    /// Precedence: defaults < file < remote config < environment variables.
+    /// The remote source is only fetched when `remote_config_url` is set by the
+    /// local file or environment. On fetch failure the local configuration is
+    /// used, unless `remote_config_required` is set.
+    ///
+    /// # Returns
+    /// - `Ok(AppConfig)` - Successfully loaded and validated configuration
+    /// - `Err(ConfigError)` - Configuration loading, fetching or validation failed
+    pub async fn load_with_remote() -> Result<Self, ConfigError> {
+        Self::load_layered_with_remote(DEFAULT_CONFIG_PATHS).await
+    }
+
+    /// Load configuration from a specific file path plus remote config (primarily for testing)
+    ///
+    /// # Arguments
+    /// - `config_path` - Path to the configuration file
+    ///
+    /// # Returns
+    /// - `Ok(AppConfig)` - Successfully loaded and validated configuration
+    /// - `Err(ConfigError)` - Configuration loading, fetching or validation failed
+    pub async fn load_from_file_with_remote(config_path: &str) -> Result<Self, ConfigError> {
+        Self::load_layered_with_remote(&[config_path]).await
+    }
+
+    async fn load_layered_with_remote(config_paths: &[&str]) -> Result<Self, ConfigError> {
+        let _ = dotenvy::dotenv();
+
+        let local: AppConfigRaw = Self::builder(config_paths, None)?
+            .build()?
+            .try_deserialize()?;
+
+        let Some(url) = local.remote_config_url.clone() else {
+            return Self::validate_and_convert(local);
+        };
+
+        let timeout = std::time::Duration::from_millis(local.remote_config_timeout_ms);
+        match fetch_remote_config(&url, timeout).await {
+            Ok((contents, format)) => {
+                let raw_config: AppConfigRaw =
+                    Self::builder(config_paths, Some((&contents, format)))?
+                        .build()?
+                        .try_deserialize()?;
+                Self::validate_and_convert(raw_config)
+            }
+            Err(e) if local.remote_config_required => Err(ConfigError::RemoteConfigFetch(url, e)),
+            Err(e) => {
+                tracing::warn!(%url, error = %e, "Remote config unavailable, using local configuration");
+                Self::validate_and_convert(local)
+            }
+        }
+    }
+
+    /// Build the layered config sources: defaults < files < remote < environment variables
+    fn builder(
+        config_paths: &[&str],
+        remote: Option<(&str, ::config::FileFormat)>,
+    ) -> Result<::config::ConfigBuilder<::config::builder::DefaultState>, ConfigError> {
+        let mut builder = ::config::Config::builder()
             .set_default("host", default_host())?
             .set_default("port", default_port())?
             .set_default("request_timeout_ms", default_timeout_ms())?
+            .set_default("client_body_timeout_ms", default_client_body_timeout_ms())?
+            .set_default("tcp_nodelay", default_tcp_nodelay())?
+            .set_default("proxy_protocol", "off")?
             .set_default("upstreams", default_upstreams())?
+            .set_default("max_upstreams", default_max_upstreams() as u64)?
             .set_default("cors_origins", default_cors_origins())?
-            .add_source(::config::File::with_name(config_path).required(false))
-            .add_source(::config::Environment::with_prefix("APP").separator("_"))
-            .build()?;
+            .set_default("cors_allow_credentials", false)?
+            .set_default("max_uri_length", default_max_uri_length() as u64)?
+            .set_default("max_header_count", default_max_header_count() as u64)?
+            .set_default("max_header_bytes", default_max_header_bytes() as u64)?
+            .set_default("max_body_bytes", default_max_body_bytes() as u64)?
+            .set_default("strict_content_length", false)?
+            .set_default("request_id_scheme", "uuid_v4")?
+            .set_default("trusted_proxies", Vec::<String>::new())?
+            .set_default("log_exclude_paths", default_log_exclude_paths())?
+            .set_default("access_log_rotation", "daily")?
+            .set_default("access_log_max_bytes", default_access_log_max_bytes())?
+            .set_default(
+                "access_log_max_files",
+                default_access_log_max_files() as u64,
+            )?
+            .set_default("startup_summary_json", false)?
+            .set_default("error_pages", default_error_pages())?
+            .set_default("serve_favicon", false)?
+            .set_default("chaos_enabled", false)?
+            .set_default("chaos_latency_ms", 0)?
+            .set_default("chaos_latency_probability", 0.0)?
+            .set_default("chaos_error_probability", 0.0)?
+            .set_default("remote_config_required", false)?
+            .set_default(
+                "remote_config_timeout_ms",
+                default_remote_config_timeout_ms(),
+            )?;
+
+        for path in config_paths {
+            builder = builder.add_source(::config::File::with_name(path).required(false));
+        }
 
-        let raw_config: AppConfigRaw = cfg.try_deserialize()?;
-        Self::validate_and_convert(raw_config)
+        if let Some((contents, format)) = remote {
+            builder = builder.add_source(::config::File::from_str(contents, format));
+        }
+
+        Ok(builder.add_source(::config::Environment::with_prefix("APP").separator("_")))
     }
 
     /// Validate raw configuration and convert to validated AppConfig
@@ -162,24 +562,58 @@ impl AppConfig {
             return Err(ConfigError::InvalidTimeout(raw.request_timeout_ms));
         }
 
-        // Validate upstream URLs
-        for (service_name, url_str) in &raw.upstreams {
-            if let Err(e) = Url::parse(url_str) {
-                return Err(ConfigError::InvalidUpstreamUrl(
-                    service_name.clone(),
-                    format!("Invalid URL format: {}", e),
-                ));
+        // Validate client body timeout
+        if raw.client_body_timeout_ms == 0 || raw.client_body_timeout_ms > 300000 {
+            return Err(ConfigError::InvalidTimeout(raw.client_body_timeout_ms));
+        }
+
+        // Validate socket buffer sizes
+        for (name, value) in [
+            ("socket_recv_buffer_bytes", raw.socket_recv_buffer_bytes),
+            ("socket_send_buffer_bytes", raw.socket_send_buffer_bytes),
+        ] {
+            if let Some(bytes) = value {
+                if !(4096..=67_108_864).contains(&bytes) {
+                    return Err(ConfigError::InvalidSocketBuffer(name.to_string(), bytes));
+                }
             }
+        }
 
-            // Check for valid scheme (http/https)
-            if let Ok(url) = Url::parse(url_str) {
-                if !matches!(url.scheme(), "http" | "https") {
+        // Validate connection limit
+        if raw.max_connections == Some(0) {
+            return Err(ConfigError::InvalidMaxConnections(0));
+        }
+
+        // Guard against accidentally huge upstream maps
+        if raw.upstreams.len() > raw.max_upstreams {
+            return Err(ConfigError::TooManyUpstreams(
+                raw.upstreams.len(),
+                raw.max_upstreams,
+            ));
+        }
+
+        // Validate upstream URLs and keep the parsed form
+        let mut upstreams = HashMap::with_capacity(raw.upstreams.len());
+        for (service_name, url_str) in raw.upstreams {
+            let url = match Url::parse(&url_str) {
+                Ok(url) => url,
+                Err(e) => {
                     return Err(ConfigError::InvalidUpstreamUrl(
-                        service_name.clone(),
-                        "URL must use http or https scheme".to_string(),
+                        service_name,
+                        format!("Invalid URL format: {}", e),
                     ));
                 }
+            };
+
+            // Check for valid scheme (http/https)
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(ConfigError::InvalidUpstreamUrl(
+                    service_name,
+                    "URL must use http or https scheme".to_string(),
+                ));
             }
+
+            upstreams.insert(service_name, url);
         }
 
         // Validate CORS origins
@@ -201,16 +635,269 @@ impl AppConfig {
             }
         }
 
+        // Browsers reject credentialed responses with a wildcard origin
+        if raw.cors_allow_credentials && raw.cors_origins.iter().any(|origin| origin == "*") {
+            return Err(ConfigError::InvalidCorsOrigin(
+                "cors_allow_credentials cannot be combined with \"*\"; list specific origins"
+                    .to_string(),
+            ));
+        }
+
+        // Validate URI length limit
+        if raw.max_uri_length == 0 {
+            return Err(ConfigError::InvalidMaxUriLength(raw.max_uri_length));
+        }
+
+        // Validate header limits
+        if raw.max_header_count == 0 {
+            return Err(ConfigError::InvalidHeaderLimit(
+                "max_header_count".to_string(),
+            ));
+        }
+        if raw.max_header_bytes == 0 {
+            return Err(ConfigError::InvalidHeaderLimit(
+                "max_header_bytes".to_string(),
+            ));
+        }
+
+        // Validate body size limit
+        if raw.max_body_bytes == 0 {
+            return Err(ConfigError::InvalidMaxBodyBytes(raw.max_body_bytes));
+        }
+
+        // Validate HTTP/1.0 default host (must be a usable Host header value)
+        if let Some(host) = &raw.http10_default_host {
+            let valid = !host.is_empty()
+                && host
+                    .parse::<axum::http::uri::Authority>()
+                    .is_ok_and(|authority| authority.as_str() == host && !host.contains('@'));
+            if !valid {
+                return Err(ConfigError::InvalidHttp10DefaultHost(host.clone()));
+            }
+        }
+
+        // Validate trusted proxies (bare IPs are treated as single-host networks)
+        let mut trusted_proxies = Vec::with_capacity(raw.trusted_proxies.len());
+        for entry in &raw.trusted_proxies {
+            let net = entry
+                .parse::<IpNet>()
+                .or_else(|_| entry.parse::<std::net::IpAddr>().map(IpNet::from))
+                .map_err(|_| ConfigError::InvalidTrustedProxy(entry.clone()))?;
+            trusted_proxies.push(net);
+        }
+
+        // Validate log exclude paths
+        for path in &raw.log_exclude_paths {
+            if !path.starts_with('/') {
+                return Err(ConfigError::InvalidLogExcludePath(path.clone()));
+            }
+        }
+
+        // Validate access log settings
+        if let Some(path) = &raw.access_log_path {
+            if path.is_empty() {
+                return Err(ConfigError::InvalidAccessLog(
+                    "access_log_path must not be empty".to_string(),
+                ));
+            }
+        }
+        if raw.access_log_max_bytes < 1024 {
+            return Err(ConfigError::InvalidAccessLog(format!(
+                "access_log_max_bytes must be at least 1024, got {}",
+                raw.access_log_max_bytes
+            )));
+        }
+        if raw.access_log_max_files == 0 {
+            return Err(ConfigError::InvalidAccessLog(
+                "access_log_max_files must be at least 1".to_string(),
+            ));
+        }
+
+        // Validate error pages (4xx/5xx status codes pointing at existing files)
+        let mut error_pages = HashMap::with_capacity(raw.error_pages.len());
+        for (status_str, path) in raw.error_pages {
+            let status = match status_str.parse::<u16>() {
+                Ok(status) if (400..=599).contains(&status) => status,
+                _ => {
+                    return Err(ConfigError::InvalidErrorPage(
+                        status_str,
+                        "Status code must be between 400 and 599".to_string(),
+                    ));
+                }
+            };
+
+            if !std::path::Path::new(&path).is_file() {
+                return Err(ConfigError::InvalidErrorPage(
+                    status_str,
+                    format!("File not found: {}", path),
+                ));
+            }
+
+            error_pages.insert(status, path);
+        }
+
+        // Validate favicon file
+        if let Some(path) = &raw.favicon_path {
+            if !std::path::Path::new(path).is_file() {
+                return Err(ConfigError::InvalidFaviconPath(path.clone()));
+            }
+        }
+
+        // Validate chaos settings (even when disabled, so enabling can't fail later)
+        if raw.chaos_latency_ms > 300000 {
+            return Err(ConfigError::InvalidChaos(format!(
+                "chaos_latency_ms must be at most 300000ms, got {}",
+                raw.chaos_latency_ms
+            )));
+        }
+        for (name, probability) in [
+            ("chaos_latency_probability", raw.chaos_latency_probability),
+            ("chaos_error_probability", raw.chaos_error_probability),
+        ] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(ConfigError::InvalidChaos(format!(
+                    "{} must be between 0.0 and 1.0, got {}",
+                    name, probability
+                )));
+            }
+        }
+
+        // Validate remote config settings
+        if let Some(url_str) = &raw.remote_config_url {
+            match Url::parse(url_str) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                Ok(_) => {
+                    return Err(ConfigError::InvalidRemoteConfig(
+                        "remote_config_url must use http or https scheme".to_string(),
+                    ));
+                }
+                Err(e) => {
+                    return Err(ConfigError::InvalidRemoteConfig(format!(
+                        "Invalid remote_config_url: {}",
+                        e
+                    )));
+                }
+            }
+        }
+        if raw.remote_config_timeout_ms == 0 || raw.remote_config_timeout_ms > 300000 {
+            return Err(ConfigError::InvalidRemoteConfig(format!(
+                "remote_config_timeout_ms must be between 1 and 300000ms, got {}",
+                raw.remote_config_timeout_ms
+            )));
+        }
+
         Ok(AppConfig {
             host: raw.host,
             port: raw.port,
             request_timeout_ms: raw.request_timeout_ms,
-            upstreams: raw.upstreams,
+            client_body_timeout_ms: raw.client_body_timeout_ms,
+            tcp_nodelay: raw.tcp_nodelay,
+            socket_recv_buffer_bytes: raw.socket_recv_buffer_bytes,
+            socket_send_buffer_bytes: raw.socket_send_buffer_bytes,
+            max_connections: raw.max_connections,
+            proxy_protocol: raw.proxy_protocol,
+            upstreams,
+            max_upstreams: raw.max_upstreams,
             cors_origins: raw.cors_origins,
+            cors_allow_credentials: raw.cors_allow_credentials,
+            max_uri_length: raw.max_uri_length,
+            max_header_count: raw.max_header_count,
+            max_header_bytes: raw.max_header_bytes,
+            max_body_bytes: raw.max_body_bytes,
+            strict_content_length: raw.strict_content_length,
+            http10_default_host: raw.http10_default_host,
+            request_id_scheme: raw.request_id_scheme,
+            trusted_proxies,
+            log_exclude_paths: raw.log_exclude_paths,
+            access_log_path: raw.access_log_path,
+            access_log_rotation: raw.access_log_rotation,
+            access_log_max_bytes: raw.access_log_max_bytes,
+            access_log_max_files: raw.access_log_max_files,
+            startup_summary_json: raw.startup_summary_json,
+            error_pages,
+            serve_favicon: raw.serve_favicon,
+            favicon_path: raw.favicon_path,
+            chaos_enabled: raw.chaos_enabled,
+            chaos_latency_ms: raw.chaos_latency_ms,
+            chaos_latency_probability: raw.chaos_latency_probability,
+            chaos_error_probability: raw.chaos_error_probability,
+            remote_config_url: raw.remote_config_url,
+            remote_config_required: raw.remote_config_required,
+            remote_config_timeout_ms: raw.remote_config_timeout_ms,
         })
     }
 }
 
+// ============================================================================
+// Remote Configuration
+// ============================================================================
+
+/// Number of attempts made to fetch remote configuration
+const REMOTE_CONFIG_ATTEMPTS: u32 = 3;
+
+/// Fetch remote configuration, retrying transient failures
+///
+/// The format is taken from the `Content-Type` header, falling back to the URL
+/// extension and then TOML.
+///
+/// # Returns
+/// - `Ok((contents, format))` - Config text and its detected format
+/// - `Err(String)` - All attempts failed (last error message)
+async fn fetch_remote_config(
+    url: &str,
+    timeout: std::time::Duration,
+) -> Result<(String, ::config::FileFormat), String> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut last_error = String::new();
+    for attempt in 1..=REMOTE_CONFIG_ATTEMPTS {
+        match fetch_remote_config_once(&client, url).await {
+            Ok(fetched) => return Ok(fetched),
+            Err(e) => {
+                tracing::warn!(%url, attempt, error = %e, "Failed to fetch remote config");
+                last_error = e;
+            }
+        }
+
+        if attempt < REMOTE_CONFIG_ATTEMPTS {
+            tokio::time::sleep(std::time::Duration::from_millis(250 * u64::from(attempt))).await;
+        }
+    }
+
+    Err(last_error)
+}
+
+async fn fetch_remote_config_once(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(String, ::config::FileFormat), String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    let format = if content_type.contains("json") || url.ends_with(".json") {
+        ::config::FileFormat::Json
+    } else {
+        ::config::FileFormat::Toml
+    };
+
+    let contents = response.text().await.map_err(|e| e.to_string())?;
+    Ok((contents, format))
+}
+
 // ============================================================================
 // Utility Methods
 // ============================================================================
@@ -232,15 +919,74 @@ impl AppConfig {
         std::time::Duration::from_millis(self.request_timeout_ms)
     }
 
-    /// Get upstream URL for a service name
+    /// Get client request body timeout as Duration
+    pub fn client_body_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.client_body_timeout_ms)
+    }
+
+    /// Get request header limits for the header limits middleware
+    pub fn header_limits(&self) -> crate::limits::HeaderLimits {
+        crate::limits::HeaderLimits {
+            max_count: self.max_header_count,
+            max_bytes: self.max_header_bytes,
+        }
+    }
+
+    /// Get request body limits for the body limits middleware
+    pub fn body_limits(&self) -> crate::limits::BodyLimits {
+        crate::limits::BodyLimits {
+            max_bytes: self.max_body_bytes,
+            strict_length: self.strict_content_length,
+            timeout: self.client_body_timeout(),
+        }
+    }
+
+    /// Get the HTTP/1.0 default Host header value for the HTTP/1.0 Host middleware
+    pub fn http10_default_host(&self) -> Option<axum::http::HeaderValue> {
+        self.http10_default_host
+            .as_deref()
+            .and_then(|host| axum::http::HeaderValue::from_str(host).ok())
+    }
+
+    /// Get listener socket buffer sizes
+    pub fn socket_buffers(&self) -> crate::listener::SocketBuffers {
+        crate::listener::SocketBuffers {
+            recv_bytes: self.socket_recv_buffer_bytes,
+            send_bytes: self.socket_send_buffer_bytes,
+        }
+    }
+
+    /// Get fault injection settings, or `None` unless `chaos_enabled` is set
+    pub fn chaos(&self) -> Option<crate::chaos::Chaos> {
+        self.chaos_enabled.then(|| crate::chaos::Chaos {
+            latency: std::time::Duration::from_millis(self.chaos_latency_ms),
+            latency_probability: self.chaos_latency_probability,
+            error_probability: self.chaos_error_probability,
+        })
+    }
+
+    /// Get parsed upstream URL for a service name
     ///
     /// # Arguments
     /// - `service_name` - Name of the upstream service
     ///
     /// # Returns
-    /// - `Some(&String)` - URL of the service if found
+    /// - `Some(&Url)` - URL of the service if found
     /// - `None` - Service not configured
-    pub fn get_upstream_url(&self, service_name: &str) -> Option<&String> {
+    pub fn get_upstream(&self, service_name: &str) -> Option<&Url> {
         self.upstreams.get(service_name)
     }
+
+    /// Get upstream URL string for a service name
+    ///
+    /// # Arguments
+    /// - `service_name` - Name of the upstream service
+    ///
+    /// # Returns
+    /// - `Some(&str)` - Normalized URL of the service if found
+    /// - `None` - Service not configured
+    #[deprecated(note = "use `get_upstream`, which returns the parsed `Url`")]
+    pub fn get_upstream_url(&self, service_name: &str) -> Option<&str> {
+        self.get_upstream(service_name).map(Url::as_str)
+    }
 }
@@ -0,0 +1,110 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+
+/// Static HTML error pages keyed by HTTP status code
+///
+/// Files are read once at startup and served from memory afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorPages {
+    pages: HashMap<u16, Bytes>,
+}
+
+impl ErrorPages {
+    /// Read every configured error page into memory
+    ///
+    /// # Arguments
+    /// - `paths` - Status code to file path mappings (see `AppConfig::error_pages`)
+    ///
+    /// # Returns
+    /// - `Ok(ErrorPages)` - All files were read successfully
+    /// - `Err(std::io::Error)` - A configured file is missing or unreadable
+    pub fn load(paths: &HashMap<u16, String>) -> std::io::Result<Self> {
+        let mut pages = HashMap::with_capacity(paths.len());
+        for (status, path) in paths {
+            let html = std::fs::read_to_string(path).map_err(|e| {
+                std::io::Error::new(e.kind(), format!("error page for {status} ({path}): {e}"))
+            })?;
+            pages.insert(*status, Bytes::from(html));
+        }
+        Ok(Self { pages })
+    }
+
+    /// Get the cached page for a status code
+    pub fn get(&self, status: u16) -> Option<&Bytes> {
+        self.pages.get(&status)
+    }
+}
+
+/// Check whether an `Accept` header prefers `text/html` over `application/json`
+///
+/// Wildcards are ignored so that clients sending `*/*` (curl, most SDKs) keep
+/// receiving the JSON error envelope.
+pub fn prefers_html(accept: &str) -> bool {
+    let mut html_q = 0.0_f32;
+    let mut json_q = 0.0_f32;
+
+    for range in accept.split(',') {
+        let mut parts = range.split(';');
+        let media_type = parts.next().unwrap_or("").trim();
+        let q = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        if media_type.eq_ignore_ascii_case("text/html") {
+            html_q = html_q.max(q);
+        } else if media_type.eq_ignore_ascii_case("application/json") {
+            json_q = json_q.max(q);
+        }
+    }
+
+    html_q > 0.0 && html_q >= json_q
+}
+
+/// Error page middleware that swaps error responses for configured HTML pages
+///
+/// - Only applies when the client `Accept` header prefers `text/html`
+/// - Only applies to 4xx/5xx responses with a configured page
+/// - Keeps the original status code and headers; API clients still get JSON
+pub async fn error_page_middleware(
+    State(pages): State<Arc<ErrorPages>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let wants_html = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(prefers_html);
+
+    let response = next.run(request).await;
+
+    if !wants_html {
+        return response;
+    }
+
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+
+    let Some(page) = pages.get(status.as_u16()) else {
+        return response;
+    };
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+
+    Response::from_parts(parts, Body::from(page.clone()))
+}
@@ -1,34 +1,128 @@
+pub mod access_log;
+pub mod chaos;
+pub mod client_ip;
 pub mod config;
+pub mod error_pages;
+pub mod limits;
+pub mod listener;
+pub mod logging;
+pub mod proxy_protocol;
+pub mod router;
 
-use axum::{extract::Request, http::HeaderName, middleware::Next, response::Response};
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 use uuid::Uuid;
 
+/// Request ID generation scheme used when the client sends no x-request-id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestIdScheme {
+    /// Random UUIDv4 (default)
+    #[default]
+    UuidV4,
+    /// Time-ordered UUIDv7, monotonic within the process
+    UuidV7,
+    /// Time-ordered ULID (26-char Crockford base32)
+    Ulid,
+}
+
+/// Crockford base32 alphabet used by ULIDs
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl RequestIdScheme {
+    /// Generate a new request ID using this scheme
+    pub fn generate(self) -> String {
+        match self {
+            RequestIdScheme::UuidV4 => Uuid::new_v4().to_string(),
+            RequestIdScheme::UuidV7 => Uuid::now_v7().to_string(),
+            RequestIdScheme::Ulid => encode_ulid(Uuid::now_v7().as_u128()),
+        }
+    }
+}
+
+/// Encode 128 bits as a ULID string
+///
+/// A UUIDv7 starts with a 48-bit millisecond timestamp, just like a ULID, so
+/// encoding one gives a valid, time-sortable ULID with 74 random bits.
+fn encode_ulid(value: u128) -> String {
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD_BASE32[((value >> (i * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+tokio::task_local! {
+    /// Request ID of the request being handled, set by `request_id_middleware`
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// Get the request ID of the request handled by the current task
+///
+/// For error paths without access to the request (e.g. the panic handler).
+/// Returns `None` outside `request_id_middleware`.
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Build the standard JSON error envelope used by all gateway errors
+///
+/// Produces `{"error": <reason phrase>, "message": <message>, "status": <code>}`
+pub fn error_envelope(status: StatusCode, message: &str) -> Response {
+    let error_response = json!({
+        "error": status.canonical_reason().unwrap_or("Error"),
+        "message": message,
+        "status": status.as_u16()
+    });
+
+    (status, Json(error_response)).into_response()
+}
+
 /// Request ID middleware that ensures every request has a unique x-request-id header
 ///
 /// - Preserves client-provided x-request-id if present
-/// - Generates new UUIDv4 if missing
+/// - Generates a new ID using the configured `RequestIdScheme` if missing
 /// - Stores ID in request extensions for downstream access
 /// - Adds ID to response headers
-pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+/// - Logs at debug instead of info for requests tagged with `QuietLog`
+///   and copies the tag onto the response
+pub async fn request_id_middleware(
+    State(scheme): State<RequestIdScheme>,
+    mut request: Request,
+    next: Next,
+) -> Response {
     // Get or generate request ID
     let request_id = request
         .headers()
         .get("x-request-id")
         .and_then(|header| header.to_str().ok())
         .map(|s| s.to_string())
-        .unwrap_or_else(|| Uuid::new_v4().to_string());
+        .unwrap_or_else(|| scheme.generate());
 
     // Store in request extensions for downstream access
     request.extensions_mut().insert(request_id.clone());
 
-    // Add request_id to the current tracing span for trace middleware
-    tracing::Span::current().record("request_id", &request_id);
+    // Record request_id on the trace layer's request span (see `logging::RequestSpan`)
+    tracing::Span::current().record("request_id", tracing::field::display(&request_id));
 
-    // Log the request ID for tracing
-    tracing::info!("Processing request with ID: {}", request_id);
+    // Log the request ID for tracing (debug only for paths in log_exclude_paths)
+    let quiet = request.extensions().get::<logging::QuietLog>().copied();
+    if quiet.is_some() {
+        tracing::debug!("Processing request with ID: {}", request_id);
+    } else {
+        tracing::info!("Processing request with ID: {}", request_id);
+    }
 
-    // Process the request
-    let mut response = next.run(request).await;
+    // Process the request (the ID stays reachable from error paths like panics)
+    let mut response = CURRENT_REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .await;
 
     // Add x-request-id to response headers
     response.headers_mut().insert(
@@ -36,5 +130,10 @@ pub async fn request_id_middleware(mut request: Request, next: Next) -> Response
         request_id.parse().unwrap(),
     );
 
+    // Carry the quiet tag to the response for the trace layer's on_response
+    if let Some(quiet) = quiet {
+        response.extensions_mut().insert(quiet);
+    }
+
     response
 }
@@ -0,0 +1,200 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode, Version},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    error_envelope,
+    router::{read_body_with_timeout, ServiceError},
+};
+
+/// Per-request header limits enforced by `header_limits_middleware`
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderLimits {
+    /// Maximum number of header fields
+    pub max_count: usize,
+    /// Maximum combined size of header names and values in bytes
+    pub max_bytes: usize,
+}
+
+/// Header limits middleware that rejects oversized header sections
+///
+/// - Counts every header field, including repeated names
+/// - Sums name and value lengths across all fields
+/// - Returns 431 Request Header Fields Too Large when either limit is exceeded
+pub async fn header_limits_middleware(
+    State(limits): State<HeaderLimits>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+
+    if headers.len() > limits.max_count {
+        tracing::warn!(
+            count = headers.len(),
+            limit = limits.max_count,
+            "Rejecting request with too many headers"
+        );
+        return error_envelope(
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            "Too many request headers",
+        );
+    }
+
+    let total_bytes: usize = headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len())
+        .sum();
+
+    if total_bytes > limits.max_bytes {
+        tracing::warn!(
+            bytes = total_bytes,
+            limit = limits.max_bytes,
+            "Rejecting request with oversized headers"
+        );
+        return error_envelope(
+            StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            "Request headers are too large",
+        );
+    }
+
+    next.run(request).await
+}
+
+/// Per-request body limits enforced by `body_limits_middleware`
+#[derive(Debug, Clone, Copy)]
+pub struct BodyLimits {
+    /// Maximum declared request body size in bytes
+    pub max_bytes: usize,
+    /// Buffer bodies with a Content-Length and reject length mismatches
+    pub strict_length: bool,
+    /// Deadline for receiving a buffered body (see `client_body_timeout_ms`)
+    pub timeout: std::time::Duration,
+}
+
+/// Body limits middleware that checks the declared Content-Length
+///
+/// - Returns 400 Bad Request for an unparsable Content-Length
+/// - Returns 413 Payload Too Large when the declared length exceeds the
+///   limit, before any of the body is read
+/// - With `strict_length`, buffers the body (up to the limit) and returns
+///   400 when its actual length differs from the declared one, or 408 when
+///   the client stalls for longer than `timeout`
+pub async fn body_limits_middleware(
+    State(limits): State<BodyLimits>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let declared = match request.headers().get(header::CONTENT_LENGTH) {
+        Some(value) => match value
+            .to_str()
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+        {
+            Some(length) => Some(length),
+            None => {
+                return error_envelope(StatusCode::BAD_REQUEST, "Invalid Content-Length header");
+            }
+        },
+        None => None,
+    };
+
+    let Some(declared) = declared else {
+        return next.run(request).await;
+    };
+
+    if declared > limits.max_bytes as u64 {
+        tracing::warn!(
+            length = declared,
+            limit = limits.max_bytes,
+            "Rejecting request with oversized body"
+        );
+        return error_envelope(StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large");
+    }
+
+    if !limits.strict_length {
+        return next.run(request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let actual = match read_body_with_timeout(body, limits.timeout, limits.max_bytes).await {
+        Ok(bytes) if bytes.len() as u64 == declared => bytes,
+        Err(err @ ServiceError::ClientTimeout(_)) => return err.into_response(),
+        result => {
+            tracing::warn!(
+                declared,
+                actual = result.as_ref().map(|bytes| bytes.len()).ok(),
+                "Rejecting request whose body does not match Content-Length"
+            );
+            return error_envelope(
+                StatusCode::BAD_REQUEST,
+                "Request body does not match Content-Length",
+            );
+        }
+    };
+
+    next.run(Request::from_parts(parts, Body::from(actual)))
+        .await
+}
+
+/// URI length middleware that rejects overly long request targets
+///
+/// - Measures the path and query as received
+/// - Returns 414 URI Too Long when the length exceeds `max_uri_length`
+pub async fn uri_length_middleware(
+    State(max_uri_length): State<usize>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let length = request
+        .uri()
+        .path_and_query()
+        .map_or(0, |path_and_query| path_and_query.as_str().len());
+
+    if length > max_uri_length {
+        tracing::warn!(
+            length,
+            limit = max_uri_length,
+            "Rejecting request with overly long URI"
+        );
+        return error_envelope(StatusCode::URI_TOO_LONG, "Request URI is too long");
+    }
+
+    next.run(request).await
+}
+
+/// HTTP/1.0 Host middleware that handles HTTP/1.0 requests without a Host header
+///
+/// - With a default host, inserts it so later code can rebuild absolute URLs
+/// - Otherwise returns 400 Bad Request with `Connection: close`
+/// - HTTP/1.1 and later (where Host is mandatory) are left to hyper
+pub async fn http10_host_middleware(
+    State(default_host): State<Option<HeaderValue>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if request.version() != Version::HTTP_10 || request.headers().contains_key(header::HOST) {
+        return next.run(request).await;
+    }
+
+    match default_host {
+        Some(host) => {
+            request.headers_mut().insert(header::HOST, host);
+            next.run(request).await
+        }
+        None => {
+            tracing::warn!("Rejecting HTTP/1.0 request without Host header");
+            let mut response = error_envelope(
+                StatusCode::BAD_REQUEST,
+                "HTTP/1.0 requests must include a Host header",
+            );
+            response
+                .headers_mut()
+                .insert(header::CONNECTION, HeaderValue::from_static("close"));
+            response
+        }
+    }
+}
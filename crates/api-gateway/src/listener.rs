@@ -0,0 +1,197 @@
+use std::{
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use axum::serve::Listener;
+use socket2::{Domain, Protocol, SockRef, Socket, Type};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpListener,
+    sync::{OwnedSemaphorePermit, Semaphore},
+};
+
+/// Pending connection backlog for the listening socket
+const LISTEN_BACKLOG: i32 = 1024;
+
+/// Socket buffer sizes requested for the listener (`None` keeps the OS default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketBuffers {
+    /// SO_RCVBUF size in bytes
+    pub recv_bytes: Option<usize>,
+    /// SO_SNDBUF size in bytes
+    pub send_bytes: Option<usize>,
+}
+
+/// Bind the gateway listener, applying the configured socket buffer sizes
+///
+/// Buffer sizes are set on the listening socket before `listen`, so accepted
+/// connections inherit them on Linux and the BSDs. The kernel may clamp the
+/// values (Linux doubles them and caps them at `net.core.{r,w}mem_max`); if it
+/// rejects one outright a warning is logged and the OS default is kept.
+///
+/// # Arguments
+/// - `addr` - Bind address in `host:port` form (see `AppConfig::addr`)
+/// - `buffers` - Requested socket buffer sizes
+///
+/// # Returns
+/// - `Ok(TcpListener)` - Listener ready to be served
+/// - `Err(std::io::Error)` - Address could not be resolved or bound
+pub async fn bind(addr: &str, buffers: SocketBuffers) -> std::io::Result<TcpListener> {
+    let addr: SocketAddr = tokio::net::lookup_host(addr).await?.next().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::AddrNotAvailable,
+            format!("could not resolve bind address: {addr}"),
+        )
+    })?;
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    // Match tokio's TcpListener::bind so restarts don't hit TIME_WAIT sockets
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+
+    if let Some(bytes) = buffers.recv_bytes {
+        if let Err(err) = socket.set_recv_buffer_size(bytes) {
+            tracing::warn!("OS rejected socket receive buffer of {bytes} bytes: {err}");
+        }
+    }
+    if let Some(bytes) = buffers.send_bytes {
+        if let Err(err) = socket.set_send_buffer_size(bytes) {
+            tracing::warn!("OS rejected socket send buffer of {bytes} bytes: {err}");
+        }
+    }
+
+    socket.bind(&addr.into())?;
+    socket.listen(LISTEN_BACKLOG)?;
+    socket.set_nonblocking(true)?;
+
+    TcpListener::from_std(socket.into())
+}
+
+/// Read the receive and send buffer sizes the OS actually applied
+pub fn applied_buffer_sizes(listener: &TcpListener) -> std::io::Result<(usize, usize)> {
+    let socket = SockRef::from(listener);
+    Ok((socket.recv_buffer_size()?, socket.send_buffer_size()?))
+}
+
+/// Listener wrapper that caps the number of open connections (`max_connections`)
+///
+/// Each accepted connection holds a semaphore permit until its IO is dropped.
+/// At the limit, accepts pause and new connections wait in the kernel backlog
+/// instead of being reset; a warning is logged each time accepting stalls.
+pub struct ConnectionLimit<L> {
+    inner: L,
+    permits: Option<Arc<Semaphore>>,
+    max: usize,
+}
+
+impl<L> ConnectionLimit<L> {
+    /// Wrap a listener (`None` accepts without limit)
+    pub fn new(inner: L, max_connections: Option<usize>) -> Self {
+        Self {
+            inner,
+            permits: max_connections.map(|max| Arc::new(Semaphore::new(max))),
+            max: max_connections.unwrap_or(0),
+        }
+    }
+
+    /// Number of currently open connections (always 0 when unlimited)
+    pub fn active_connections(&self) -> usize {
+        self.permits
+            .as_ref()
+            .map_or(0, |permits| self.max - permits.available_permits())
+    }
+}
+
+impl<L: Listener> Listener for ConnectionLimit<L> {
+    type Io = LimitedIo<L::Io>;
+    type Addr = L::Addr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        let permit = match &self.permits {
+            Some(permits) => {
+                let permit = match permits.clone().try_acquire_owned() {
+                    Ok(permit) => permit,
+                    Err(_) => {
+                        tracing::warn!("Connection limit of {} reached; pausing accepts", self.max);
+                        permits
+                            .clone()
+                            .acquire_owned()
+                            .await
+                            .expect("connection semaphore is never closed")
+                    }
+                };
+                Some(permit)
+            }
+            None => None,
+        };
+
+        let (io, addr) = self.inner.accept().await;
+        (
+            LimitedIo {
+                inner: io,
+                _permit: permit,
+            },
+            addr,
+        )
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+/// Connection IO that releases its `ConnectionLimit` permit when dropped
+pub struct LimitedIo<I> {
+    inner: I,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl<I> LimitedIo<I> {
+    /// Access the underlying connection (e.g. to set socket options)
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+}
+
+impl<I: AsyncRead + Unpin> AsyncRead for LimitedIo<I> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<I: AsyncWrite + Unpin> AsyncWrite for LimitedIo<I> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,93 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{Request, State},
+    http,
+    middleware::Next,
+    response::Response,
+};
+use tower_http::trace::{DefaultOnResponse, MakeSpan, OnResponse};
+use tracing::Span;
+
+/// Request paths whose info-level request logs are suppressed (e.g. health checks)
+#[derive(Debug, Clone, Default)]
+pub struct LogExcludePaths(Arc<HashSet<String>>);
+
+impl LogExcludePaths {
+    /// Create from the configured `log_exclude_paths` list
+    pub fn new(paths: &[String]) -> Self {
+        Self(Arc::new(paths.iter().cloned().collect()))
+    }
+
+    /// Check whether a request path is excluded from info-level logging
+    pub fn is_excluded(&self, path: &str) -> bool {
+        self.0.contains(path)
+    }
+}
+
+/// Trace layer span factory that creates one `request` span per request
+///
+/// The span declares an empty `request_id` field that `request_id_middleware`
+/// fills in, so every event logged while handling the request carries the ID.
+/// Use it with `.on_request(())`: `request_id_middleware` logs the start of the
+/// request itself, after the ID has been recorded on the span.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestSpan;
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &http::Request<B>) -> Span {
+        tracing::info_span!(
+            "request",
+            method = %request.method(),
+            uri = %request.uri(),
+            version = ?request.version(),
+            headers = ?request.headers(),
+            request_id = tracing::field::Empty,
+        )
+    }
+}
+
+/// Marker stored in request and response extensions for quietly logged requests
+#[derive(Debug, Clone, Copy)]
+pub struct QuietLog;
+
+/// Quiet log middleware that tags requests to excluded paths
+///
+/// - Must run outside the trace layer and `request_id_middleware`
+/// - Tags the request with `QuietLog`; `request_id_middleware` copies the tag
+///   onto the response for the trace layer's `on_response` callback
+/// - Tagged requests still get a request ID; only info-level logs are skipped
+pub async fn quiet_log_middleware(
+    State(paths): State<LogExcludePaths>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if paths.is_excluded(request.uri().path()) {
+        request.extensions_mut().insert(QuietLog);
+    }
+
+    next.run(request).await
+}
+
+/// Trace layer `on_response` callback that skips responses tagged with `QuietLog`
+///
+/// Failures are reported through `on_failure`, which is not affected.
+#[derive(Debug, Clone)]
+pub struct QuietOnResponse {
+    inner: DefaultOnResponse,
+}
+
+impl QuietOnResponse {
+    /// Wrap a default `on_response` callback
+    pub fn new(inner: DefaultOnResponse) -> Self {
+        Self { inner }
+    }
+}
+
+impl<B> OnResponse<B> for QuietOnResponse {
+    fn on_response(self, response: &http::Response<B>, latency: Duration, span: &Span) {
+        if response.extensions().get::<QuietLog>().is_none() {
+            self.inner.on_response(response, latency, span);
+        }
+    }
+}
@@ -1,110 +1,36 @@
 use api_gateway::config::AppConfig;
-use api_gateway::request_id_middleware;
-use axum::{
-    http::{Method, StatusCode},
-    response::{IntoResponse, Response},
-    routing::get,
-    Json, Router,
-};
+use api_gateway::listener;
+use api_gateway::proxy_protocol::ProxyProtocolListener;
+use api_gateway::router::build_router;
+use axum::serve::ListenerExt;
 use serde_json::json;
-use tokio::net::TcpListener;
-use tower::ServiceBuilder;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, DefaultOnFailure};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 // ============================================================================
-// HTTP Handlers
-// ============================================================================
-
-/// Root endpoint - returns service status
-async fn root() -> &'static str {
-    "api gateway: okay"
-}
-
-/// Health check endpoint for monitoring and load balancers
-async fn health() -> &'static str {
-    "ok"
-}
-
-/// Test endpoint that simulates a slow response for timeout testing
-async fn slow_endpoint() -> Result<&'static str, ServiceError> {
-    tokio::time::sleep(tokio::time::Duration::from_secs(20)).await;
-    Ok("This should never be reached due to timeout")
-}
-
-/// Wrapper function that applies timeout to any async function
-async fn with_timeout<F, T>(duration: std::time::Duration, future: F) -> Result<T, ServiceError>
-where
-    F: std::future::Future<Output = T>,
-{
-    tokio::time::timeout(duration, future)
-        .await
-        .map_err(|_| ServiceError::Timeout(tower::timeout::error::Elapsed::new()))
-}
-
-// ============================================================================
-// Error Handling
+// Application Setup
 // ============================================================================
 
-/// Custom error type for handling various service errors
-#[derive(Debug)]
-pub enum ServiceError {
-    Timeout(tower::timeout::error::Elapsed),
-    Other(Box<dyn std::error::Error + Send + Sync>),
-}
-
-impl IntoResponse for ServiceError {
-    fn into_response(self) -> Response {
-        match self {
-            ServiceError::Timeout(err) => {
-                tracing::warn!("Request timed out: {}", err);
-
-                let error_response = json!({
-                    "error": "Gateway Timeout",
-                    "message": "The request timed out",
-                    "status": 504
-                });
-
-                (StatusCode::GATEWAY_TIMEOUT, Json(error_response)).into_response()
-            }
-            ServiceError::Other(err) => {
-                tracing::error!("Service error: {}", err);
-
-                let error_response = json!({
-                    "error": "Internal Server Error",
-                    "message": "An internal error occurred",
-                    "status": 500
-                });
-
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)).into_response()
-            }
-        }
-    }
-}
-
-impl From<tower::timeout::error::Elapsed> for ServiceError {
-    fn from(err: tower::timeout::error::Elapsed) -> Self {
-        ServiceError::Timeout(err)
-    }
+/// Build the structured startup summary emitted when `startup_summary_json` is set
+fn startup_summary(
+    cfg: &AppConfig,
+    addr: std::net::SocketAddr,
+    buffers: (usize, usize),
+) -> serde_json::Value {
+    json!({
+        "event": "startup",
+        "bind_address": addr.to_string(),
+        "tls": false,
+        "request_timeout_ms": cfg.request_timeout_ms,
+        "tcp_nodelay": cfg.tcp_nodelay,
+        "max_connections": cfg.max_connections,
+        "proxy_protocol": cfg.proxy_protocol,
+        "socket_recv_buffer_bytes": buffers.0,
+        "socket_send_buffer_bytes": buffers.1,
+        "cors_origins": cfg.cors_origins,
+        "upstream_count": cfg.upstreams.len()
+    })
 }
 
-impl From<Box<dyn std::error::Error + Send + Sync>> for ServiceError {
-    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
-        ServiceError::Other(err)
-    }
-}
-
-// ============================================================================
-// Trace Middleware
-// ============================================================================
-
-// TraceLayer will be applied directly in the router setup
-
-// ============================================================================
-// Application Setup
-// ============================================================================
-
 /// Main entry point for the API Gateway service
 ///
 /// Initializes logging, loads configuration, sets up middleware, and starts the server.
@@ -127,89 +53,100 @@ async fn main() -> Result<(), anyhow::Error> {
         .init();
 
     // Load and validate configuration
-    let cfg = AppConfig::load().map_err(|e| anyhow::anyhow!("Config error: {}", e))?;
+    let cfg = AppConfig::load_with_remote()
+        .await
+        .map_err(|e| anyhow::anyhow!("Config error: {}", e))?;
     tracing::info!(?cfg, "loaded config");
 
     let addr = cfg.addr();
 
-    // Configure CORS middleware
-    let cors_layer = if cfg.cors_origins.contains(&"*".to_string()) {
-        // Allow all origins (development mode)
-        CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([
-                axum::http::header::CONTENT_TYPE,
-                axum::http::header::AUTHORIZATION,
-                axum::http::HeaderName::from_static("x-request-id"),
-            ])
-            .expose_headers([axum::http::HeaderName::from_static("x-request-id")])
-    } else {
-        // Validate specific origins
-        let origins: Result<Vec<_>, _> = cfg
-            .cors_origins
-            .iter()
-            .map(|origin| origin.parse())
-            .collect();
-        CorsLayer::new()
-            .allow_origin(origins.map_err(|e| anyhow::anyhow!("Invalid CORS origin: {}", e))?)
-            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-            .allow_headers([
-                axum::http::header::CONTENT_TYPE,
-                axum::http::header::AUTHORIZATION,
-                axum::http::HeaderName::from_static("x-request-id"),
-            ])
-            .expose_headers([axum::http::HeaderName::from_static("x-request-id")])
-    };
-
     // Build HTTP router with middleware
-    let app = Router::new()
-        .route("/", get(root))
-        .route("/healthz", get(health))
-        .route(
-            "/slow",
-            get({
-                let timeout_duration = cfg.timeout_duration();
-                move || async move { with_timeout(timeout_duration, slow_endpoint()).await }
-            }),
-        )
-        .layer(axum::middleware::from_fn(request_id_middleware))
-        .layer(
-            tower_http::trace::TraceLayer::new_for_http()
-                .make_span_with(
-                    DefaultMakeSpan::new()
-                        .include_headers(true)
-                        .level(tracing::Level::INFO)
-                )
-                .on_request(
-                    DefaultOnRequest::new()
-                        .level(tracing::Level::INFO)
-                )
-                .on_response(
-                    DefaultOnResponse::new()
-                        .level(tracing::Level::INFO)
-                )
-                .on_failure(
-                    DefaultOnFailure::new()
-                        .level(tracing::Level::ERROR)
-                )
-        )
-        .layer(ServiceBuilder::new().layer(cors_layer));
+    let app = build_router(&cfg)?;
 
     // Start server
-    let listener = TcpListener::bind(&addr).await?;
+    let listener = listener::bind(&addr, cfg.socket_buffers()).await?;
     let actual_addr = listener.local_addr()?;
+    let (recv_buffer, send_buffer) = listener::applied_buffer_sizes(&listener)?;
+
+    // Cap open connections to protect against fd exhaustion; applied before
+    // PROXY protocol so connections still reading their header are counted
+    let listener = listener::ConnectionLimit::new(listener, cfg.max_connections);
+
+    // Recover real client addresses from PROXY protocol headers
+    let listener = ProxyProtocolListener::new(listener, cfg.proxy_protocol)?;
+
+    // Apply TCP_NODELAY to every accepted connection
+    let tcp_nodelay = cfg.tcp_nodelay;
+    let listener = listener.tap_io(move |conn| {
+        if let Err(err) = conn.get_mut().get_mut().set_nodelay(tcp_nodelay) {
+            tracing::trace!("failed to set TCP_NODELAY on incoming connection: {err:#}");
+        }
+    });
 
-    tracing::info!("🚀 API Gateway started successfully");
-    tracing::info!("📍 Listening on: http://{}", actual_addr);
-    tracing::info!("🔧 Host binding: {} ({})", 
-        if cfg.host.is_empty() { "all interfaces (0.0.0.0)" } else { &cfg.host },
-        if cfg.host.is_empty() { "external access enabled" } else { "localhost only" }
-    );
-    tracing::info!("⏱️  Request timeout: {}ms", cfg.request_timeout_ms);
-    tracing::info!("🌐 CORS origins: {:?}", cfg.cors_origins);
-    tracing::info!("🔗 Upstream services: {:?}", cfg.upstreams);
+    if cfg.startup_summary_json {
+        // Single machine-parseable event for log pipelines
+        tracing::info!(summary = %startup_summary(&cfg, actual_addr, (recv_buffer, send_buffer)), "startup summary");
+    } else {
+        tracing::info!("🚀 API Gateway started successfully");
+        tracing::info!("📍 Listening on: http://{}", actual_addr);
+        tracing::info!(
+            "🔧 Host binding: {} ({})",
+            if cfg.host.is_empty() {
+                "all interfaces (0.0.0.0)"
+            } else {
+                &cfg.host
+            },
+            if cfg.host.is_empty() {
+                "external access enabled"
+            } else {
+                "localhost only"
+            }
+        );
+        tracing::info!("⏱️  Request timeout: {}ms", cfg.request_timeout_ms);
+        tracing::info!(
+            "📶 TCP_NODELAY: {}",
+            if cfg.tcp_nodelay {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        tracing::info!(
+            "🔌 Max connections: {}",
+            cfg.max_connections
+                .map_or("unlimited".to_string(), |max| max.to_string())
+        );
+        tracing::info!("🛰️  PROXY protocol: {:?}", cfg.proxy_protocol);
+        tracing::info!(
+            "📦 Socket buffers: recv {} bytes{}, send {} bytes{}",
+            recv_buffer,
+            if cfg.socket_recv_buffer_bytes.is_some() {
+                ""
+            } else {
+                " (OS default)"
+            },
+            send_buffer,
+            if cfg.socket_send_buffer_bytes.is_some() {
+                ""
+            } else {
+                " (OS default)"
+            }
+        );
+        tracing::info!("🌐 CORS origins: {:?}", cfg.cors_origins);
+        tracing::info!(
+            "🔗 Upstream services: {:?}",
+            cfg.upstreams
+                .iter()
+                .map(|(name, url)| (name.as_str(), url.as_str()))
+                .collect::<std::collections::HashMap<_, _>>()
+        );
+    }
 
-    axum::serve(listener, app).await?;
+    // Peer addresses feed client IP resolution (see trusted_proxies)
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
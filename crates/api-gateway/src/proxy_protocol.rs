@@ -0,0 +1,418 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use axum::serve::Listener;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+/// PROXY protocol v1 signature (text format)
+const V1_SIGNATURE: &[u8] = b"PROXY ";
+
+/// PROXY protocol v1 maximum header length including CRLF
+const V1_MAX_LEN: usize = 107;
+
+/// PROXY protocol v2 signature (binary format)
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// PROXY protocol v2 fixed header length (signature, version/command, family, length)
+const V2_HEADER_LEN: usize = 16;
+
+/// Time a client has to send its PROXY protocol header
+const HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connections with a parsed header waiting to be accepted by the server
+const ACCEPT_QUEUE: usize = 1024;
+
+/// Whether accepted connections start with a PROXY protocol header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyProtocolMode {
+    /// No header expected; the socket peer is the client (default)
+    #[default]
+    Off,
+    /// Use the header when present, otherwise the socket peer
+    Optional,
+    /// Close connections that don't start with a valid header
+    Required,
+}
+
+/// PROXY protocol header errors
+#[derive(Debug, Error)]
+pub enum ProxyProtocolError {
+    /// Header is malformed or uses an unsupported version
+    #[error("Invalid PROXY protocol header: {0}")]
+    Invalid(String),
+
+    /// Connection didn't start with a header in `Required` mode
+    #[error("Missing PROXY protocol header")]
+    Missing,
+
+    /// Client didn't send the full header in time
+    #[error("Timed out reading PROXY protocol header")]
+    Timeout,
+
+    /// Connection failed or closed while reading the header
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Result of inspecting the start of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseOutcome {
+    /// More bytes are needed to decide
+    Incomplete,
+    /// The connection doesn't start with a PROXY protocol signature
+    NotProxy,
+    /// A complete header of `len` bytes; `source` is `None` for LOCAL/UNKNOWN
+    Header {
+        source: Option<SocketAddr>,
+        len: usize,
+    },
+}
+
+/// Parse a PROXY protocol v1 or v2 header from the start of `buf`
+///
+/// # Returns
+/// - `Ok(ParseOutcome)` - Header parsed, more bytes needed, or no header
+/// - `Err(ProxyProtocolError::Invalid)` - A signature matched but the header is malformed
+pub fn parse_header(buf: &[u8]) -> Result<ParseOutcome, ProxyProtocolError> {
+    if buf.is_empty() {
+        return Ok(ParseOutcome::Incomplete);
+    }
+
+    if starts_with_partial(buf, V2_SIGNATURE) {
+        if buf.len() < V2_SIGNATURE.len() {
+            return Ok(ParseOutcome::Incomplete);
+        }
+        return parse_v2(buf);
+    }
+
+    if starts_with_partial(buf, V1_SIGNATURE) {
+        if buf.len() < V1_SIGNATURE.len() {
+            return Ok(ParseOutcome::Incomplete);
+        }
+        return parse_v1(buf);
+    }
+
+    Ok(ParseOutcome::NotProxy)
+}
+
+/// Check whether `buf` and `signature` agree on their common prefix
+fn starts_with_partial(buf: &[u8], signature: &[u8]) -> bool {
+    let len = buf.len().min(signature.len());
+    buf[..len] == signature[..len]
+}
+
+/// Parse a v1 header: `PROXY TCP4 <src> <dst> <sport> <dport>\r\n`
+fn parse_v1(buf: &[u8]) -> Result<ParseOutcome, ProxyProtocolError> {
+    let search = &buf[..buf.len().min(V1_MAX_LEN)];
+    let Some(end) = search.windows(2).position(|w| w == b"\r\n") else {
+        if buf.len() < V1_MAX_LEN {
+            return Ok(ParseOutcome::Incomplete);
+        }
+        return Err(ProxyProtocolError::Invalid(
+            "v1 header exceeds 107 bytes".to_string(),
+        ));
+    };
+
+    let line = std::str::from_utf8(&buf[..end])
+        .map_err(|_| ProxyProtocolError::Invalid("v1 header is not ASCII".to_string()))?;
+    let parts: Vec<&str> = line.split(' ').collect();
+    let len = end + 2;
+
+    match parts.get(1).copied() {
+        Some("UNKNOWN") => Ok(ParseOutcome::Header { source: None, len }),
+        Some(proto @ ("TCP4" | "TCP6")) => {
+            let [_, _, src, _dst, sport, _dport] = parts[..] else {
+                return Err(ProxyProtocolError::Invalid(format!(
+                    "v1 header has {} fields, expected 6",
+                    parts.len()
+                )));
+            };
+            let ip: IpAddr = match proto {
+                "TCP4" => src.parse::<Ipv4Addr>().map(IpAddr::V4),
+                _ => src.parse::<Ipv6Addr>().map(IpAddr::V6),
+            }
+            .map_err(|_| ProxyProtocolError::Invalid(format!("bad source address: {src}")))?;
+            let port: u16 = sport
+                .parse()
+                .map_err(|_| ProxyProtocolError::Invalid(format!("bad source port: {sport}")))?;
+
+            Ok(ParseOutcome::Header {
+                source: Some(SocketAddr::new(ip, port)),
+                len,
+            })
+        }
+        other => Err(ProxyProtocolError::Invalid(format!(
+            "unsupported v1 protocol: {}",
+            other.unwrap_or("")
+        ))),
+    }
+}
+
+/// Parse a v2 header (binary; only the source address is extracted)
+fn parse_v2(buf: &[u8]) -> Result<ParseOutcome, ProxyProtocolError> {
+    if buf.len() < V2_HEADER_LEN {
+        return Ok(ParseOutcome::Incomplete);
+    }
+
+    let version = buf[12] >> 4;
+    let command = buf[12] & 0x0f;
+    let family = buf[13] >> 4;
+    let addr_len = u16::from_be_bytes([buf[14], buf[15]]) as usize;
+    let len = V2_HEADER_LEN + addr_len;
+
+    if version != 2 {
+        return Err(ProxyProtocolError::Invalid(format!(
+            "unsupported v2 version: {version}"
+        )));
+    }
+    if buf.len() < len {
+        return Ok(ParseOutcome::Incomplete);
+    }
+
+    let addr = &buf[V2_HEADER_LEN..len];
+    let source = match (command, family) {
+        // LOCAL: health checks from the proxy itself, use the socket peer
+        (0x0, _) => None,
+        (0x1, 0x1) if addr.len() >= 12 => {
+            let ip = Ipv4Addr::new(addr[0], addr[1], addr[2], addr[3]);
+            let port = u16::from_be_bytes([addr[8], addr[9]]);
+            Some(SocketAddr::new(IpAddr::V4(ip), port))
+        }
+        (0x1, 0x2) if addr.len() >= 36 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&addr[..16]);
+            let port = u16::from_be_bytes([addr[32], addr[33]]);
+            Some(SocketAddr::new(IpAddr::V6(Ipv6Addr::from(octets)), port))
+        }
+        // AF_UNSPEC, AF_UNIX: no usable IP address
+        (0x1, 0x0 | 0x3) => None,
+        (0x1, _) => {
+            return Err(ProxyProtocolError::Invalid(format!(
+                "v2 address block too short for family {family}"
+            )));
+        }
+        _ => {
+            return Err(ProxyProtocolError::Invalid(format!(
+                "unsupported v2 command: {command}"
+            )));
+        }
+    };
+
+    Ok(ParseOutcome::Header { source, len })
+}
+
+/// Read and strip the PROXY protocol header from a new connection
+///
+/// # Returns
+/// - `Ok((PrefixedIo, SocketAddr))` - Connection (with any bytes read past
+///   the header replayed) and the client address
+/// - `Err(ProxyProtocolError)` - The connection must be closed
+pub async fn read_header<I: AsyncRead + Unpin>(
+    mut stream: I,
+    peer: SocketAddr,
+    mode: ProxyProtocolMode,
+) -> Result<(PrefixedIo<I>, SocketAddr), ProxyProtocolError> {
+    let mut buf = Vec::with_capacity(V1_MAX_LEN);
+    let mut chunk = [0u8; 512];
+
+    loop {
+        match parse_header(&buf)? {
+            ParseOutcome::Header { source, len } => {
+                let rest = buf.split_off(len);
+                return Ok((PrefixedIo::new(stream, rest), source.unwrap_or(peer)));
+            }
+            ParseOutcome::NotProxy if mode == ProxyProtocolMode::Required => {
+                return Err(ProxyProtocolError::Missing);
+            }
+            ParseOutcome::NotProxy => return Ok((PrefixedIo::new(stream, buf), peer)),
+            ParseOutcome::Incomplete => {}
+        }
+
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(ProxyProtocolError::Io(
+                std::io::ErrorKind::UnexpectedEof.into(),
+            ));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Listener that strips PROXY protocol headers and reports the real client address
+///
+/// With `Optional` or `Required` mode, headers are read on a background task
+/// per connection (bounded by a 5 second timeout), so one slow client can't
+/// stall accepts. Connections with invalid or missing headers are closed.
+///
+/// The background task accepts from the inner listener, so wrap a
+/// `ConnectionLimit` (as `main` does) to count connections whose header is
+/// still being read or that are queued for the server.
+///
+/// axum only implements `Connected` for its own listener types, so wrap this
+/// in `tap_io` (as `main` does) to serve with `ConnectInfo<SocketAddr>`.
+pub struct ProxyProtocolListener<L: Listener = TcpListener> {
+    local_addr: SocketAddr,
+    source: Source<L>,
+}
+
+/// Where `ProxyProtocolListener` gets its connections from
+enum Source<L: Listener> {
+    /// PROXY protocol off: accept directly
+    Direct(L),
+    /// Connections whose header has been read by the accept task
+    Parsed(mpsc::Receiver<(PrefixedIo<L::Io>, SocketAddr)>),
+}
+
+impl<L: Listener<Addr = SocketAddr>> ProxyProtocolListener<L> {
+    /// Wrap a bound listener (must be called inside a Tokio runtime)
+    pub fn new(listener: L, mode: ProxyProtocolMode) -> std::io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+
+        let source = match mode {
+            ProxyProtocolMode::Off => Source::Direct(listener),
+            mode => {
+                let (tx, rx) = mpsc::channel(ACCEPT_QUEUE);
+                tokio::spawn(accept_loop(listener, mode, tx));
+                Source::Parsed(rx)
+            }
+        };
+
+        Ok(Self { local_addr, source })
+    }
+}
+
+/// Accept connections and read their headers until the listener is dropped
+async fn accept_loop<L: Listener<Addr = SocketAddr>>(
+    mut listener: L,
+    mode: ProxyProtocolMode,
+    tx: mpsc::Sender<(PrefixedIo<L::Io>, SocketAddr)>,
+) {
+    loop {
+        // A `ConnectionLimit` inner listener waits for a permit before accepting,
+        // and the permit travels with the stream until the connection closes
+        let (stream, peer) = listener.accept().await;
+        let tx = tx.clone();
+        if tx.is_closed() {
+            return;
+        }
+
+        tokio::spawn(async move {
+            match tokio::time::timeout(HEADER_TIMEOUT, read_header(stream, peer, mode)).await {
+                Ok(Ok(conn)) => {
+                    let _ = tx.send(conn).await;
+                }
+                Ok(Err(err)) => {
+                    tracing::debug!(%peer, "Closing connection: {}", err);
+                }
+                Err(_) => {
+                    tracing::debug!(%peer, "Closing connection: {}", ProxyProtocolError::Timeout);
+                }
+            }
+        });
+    }
+}
+
+impl<L: Listener<Addr = SocketAddr>> Listener for ProxyProtocolListener<L> {
+    type Io = PrefixedIo<L::Io>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match &mut self.source {
+            Source::Direct(listener) => {
+                let (stream, addr) = Listener::accept(listener).await;
+                (PrefixedIo::new(stream, Vec::new()), addr)
+            }
+            Source::Parsed(rx) => match rx.recv().await {
+                Some(conn) => conn,
+                // The accept task only exits once the receiver is gone
+                None => std::future::pending().await,
+            },
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// Connection IO that replays bytes read past the PROXY protocol header
+pub struct PrefixedIo<I> {
+    inner: I,
+    prefix: Vec<u8>,
+    offset: usize,
+}
+
+impl<I> PrefixedIo<I> {
+    /// Wrap a connection, replaying `prefix` before reading from it
+    pub fn new(inner: I, prefix: Vec<u8>) -> Self {
+        Self {
+            inner,
+            prefix,
+            offset: 0,
+        }
+    }
+
+    /// Access the underlying connection (e.g. to set socket options)
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+}
+
+impl<I: AsyncRead + Unpin> AsyncRead for PrefixedIo<I> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        if self.offset < self.prefix.len() {
+            let remaining = &self.prefix[self.offset..];
+            let len = remaining.len().min(buf.remaining());
+            buf.put_slice(&remaining[..len]);
+            self.offset += len;
+            return Poll::Ready(Ok(()));
+        }
+
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<I: AsyncWrite + Unpin> AsyncWrite for PrefixedIo<I> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[std::io::IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
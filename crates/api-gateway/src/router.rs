@@ -0,0 +1,411 @@
+use axum::{
+    body::Bytes,
+    http::{header, Method, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde_json::json;
+use tower::ServiceBuilder;
+use tower_http::{
+    cors::{Any, CorsLayer},
+    trace::{DefaultOnFailure, DefaultOnResponse},
+};
+
+use crate::{
+    access_log::{access_log_middleware, AccessLog},
+    chaos::chaos_middleware,
+    client_ip::{client_ip_middleware, TrustedProxies},
+    config::AppConfig,
+    current_request_id,
+    error_pages::{error_page_middleware, ErrorPages},
+    limits::{
+        body_limits_middleware, header_limits_middleware, http10_host_middleware,
+        uri_length_middleware,
+    },
+    logging::{quiet_log_middleware, LogExcludePaths, QuietOnResponse, RequestSpan},
+    request_id_middleware,
+};
+
+// ============================================================================
+// HTTP Handlers
+// ============================================================================
+
+/// Root endpoint - returns service status
+async fn root() -> &'static str {
+    "api gateway: okay"
+}
+
+/// Health check endpoint for monitoring and load balancers
+async fn health() -> &'static str {
+    "ok"
+}
+
+/// Favicon endpoint - serves the configured icon, or an empty 204 so browsers
+/// stop producing 404s
+async fn favicon(icon: Option<(&'static str, Bytes)>) -> Response {
+    match icon {
+        Some((content_type, bytes)) => (
+            [
+                (header::CONTENT_TYPE, content_type),
+                (header::CACHE_CONTROL, "public, max-age=86400"),
+            ],
+            bytes,
+        )
+            .into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+/// Content type for a favicon file, based on its extension
+fn favicon_content_type(path: &str) -> &'static str {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    match extension.as_str() {
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "gif" => "image/gif",
+        _ => "image/x-icon",
+    }
+}
+
+/// Test endpoint that simulates a slow response for timeout testing
+async fn slow_endpoint() -> Result<&'static str, ServiceError> {
+    tokio::time::sleep(tokio::time::Duration::from_secs(20)).await;
+    Ok("This should never be reached due to timeout")
+}
+
+/// Wrapper function that applies timeout to any async function
+async fn with_timeout<F, T>(duration: std::time::Duration, future: F) -> Result<T, ServiceError>
+where
+    F: std::future::Future<Output = T>,
+{
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| ServiceError::Timeout(tower::timeout::error::Elapsed::new()))
+}
+
+/// Read a request body, failing with `ServiceError::ClientTimeout` if the
+/// client doesn't finish sending it within `timeout`
+///
+/// Used by `body_limits_middleware` for `strict_content_length`, and by
+/// embedder handlers that consume bodies; pair with
+/// `AppConfig::client_body_timeout` so stalled uploads get 408 rather than
+/// being mistaken for a slow upstream (504).
+///
+/// # Arguments
+/// - `body` - Request body to buffer
+/// - `timeout` - Deadline for receiving the whole body
+/// - `limit` - Maximum body size in bytes
+///
+/// # Returns
+/// - `Ok(Bytes)` - The complete body
+/// - `Err(ServiceError::ClientTimeout)` - The client stalled
+/// - `Err(ServiceError::Other)` - The body could not be read or was too large
+pub async fn read_body_with_timeout(
+    body: axum::body::Body,
+    timeout: std::time::Duration,
+    limit: usize,
+) -> Result<Bytes, ServiceError> {
+    match tokio::time::timeout(timeout, axum::body::to_bytes(body, limit)).await {
+        Ok(result) => result.map_err(|e| ServiceError::Other(Box::new(e))),
+        Err(_) => Err(ServiceError::ClientTimeout(
+            tower::timeout::error::Elapsed::new(),
+        )),
+    }
+}
+
+// ============================================================================
+// Error Handling
+// ============================================================================
+
+/// Custom error type for handling various service errors
+///
+/// Timeouts are split by who stalled: the gateway or upstream (504) versus
+/// the client sending its request body (408).
+#[derive(Debug)]
+pub enum ServiceError {
+    Timeout(tower::timeout::error::Elapsed),
+    ClientTimeout(tower::timeout::error::Elapsed),
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl IntoResponse for ServiceError {
+    fn into_response(self) -> Response {
+        match self {
+            ServiceError::Timeout(err) => {
+                tracing::warn!("Request timed out: {}", err);
+
+                let error_response = json!({
+                    "error": "Gateway Timeout",
+                    "message": "The request timed out",
+                    "status": 504
+                });
+
+                (StatusCode::GATEWAY_TIMEOUT, Json(error_response)).into_response()
+            }
+            ServiceError::ClientTimeout(err) => {
+                tracing::warn!("Client request body timed out: {}", err);
+
+                let error_response = json!({
+                    "error": "Request Timeout",
+                    "message": "The client did not send the request in time",
+                    "status": 408
+                });
+
+                (StatusCode::REQUEST_TIMEOUT, Json(error_response)).into_response()
+            }
+            ServiceError::Other(err) => {
+                tracing::error!("Service error: {}", err);
+
+                let error_response = json!({
+                    "error": "Internal Server Error",
+                    "message": "An internal error occurred",
+                    "status": 500
+                });
+
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)).into_response()
+            }
+        }
+    }
+}
+
+/// `CatchPanicLayer` handler that logs the panic and returns the 500 envelope
+///
+/// Runs inside `request_id_middleware`, so the log event carries the request
+/// span and the envelope includes the request ID for correlation. Relies on
+/// `panic = "unwind"` in the release profile; with `abort` this never runs.
+fn panic_response(err: Box<dyn std::any::Any + Send + 'static>) -> Response {
+    let detail = if let Some(s) = err.downcast_ref::<String>() {
+        s.as_str()
+    } else if let Some(s) = err.downcast_ref::<&str>() {
+        s
+    } else {
+        "unknown panic payload"
+    };
+    let request_id = current_request_id();
+
+    tracing::error!(request_id = ?request_id, "Handler panicked: {}", detail);
+
+    let error_response = json!({
+        "error": "Internal Server Error",
+        "message": "An internal error occurred",
+        "status": 500,
+        "request_id": request_id
+    });
+
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(error_response)).into_response()
+}
+
+impl From<tower::timeout::error::Elapsed> for ServiceError {
+    fn from(err: tower::timeout::error::Elapsed) -> Self {
+        ServiceError::Timeout(err)
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for ServiceError {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        ServiceError::Other(err)
+    }
+}
+
+// ============================================================================
+// Router Setup
+// ============================================================================
+
+/// Build the CORS layer from the configured origins
+fn cors_layer(cfg: &AppConfig) -> Result<CorsLayer, anyhow::Error> {
+    let cors_layer = if cfg.cors_origins.contains(&"*".to_string()) {
+        // Allow all origins (development mode)
+        CorsLayer::new()
+            .allow_origin(Any)
+            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+            .allow_headers([
+                axum::http::header::CONTENT_TYPE,
+                axum::http::header::AUTHORIZATION,
+                axum::http::HeaderName::from_static("x-request-id"),
+            ])
+            .expose_headers([axum::http::HeaderName::from_static("x-request-id")])
+    } else {
+        // Validate specific origins
+        let origins: Result<Vec<_>, _> = cfg
+            .cors_origins
+            .iter()
+            .map(|origin| origin.parse())
+            .collect();
+        CorsLayer::new()
+            .allow_origin(origins.map_err(|e| anyhow::anyhow!("Invalid CORS origin: {}", e))?)
+            .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+            .allow_headers([
+                axum::http::header::CONTENT_TYPE,
+                axum::http::header::AUTHORIZATION,
+                axum::http::HeaderName::from_static("x-request-id"),
+            ])
+            .expose_headers([axum::http::HeaderName::from_static("x-request-id")])
+            // Origins are echoed individually, so credentials are allowed here
+            .allow_credentials(cfg.cors_allow_credentials)
+    };
+
+    Ok(cors_layer)
+}
+
+/// Build the gateway router with the full middleware stack
+///
+/// # Arguments
+/// - `cfg` - Validated application configuration
+///
+/// # Returns
+/// - `Ok(Router)` - Router ready to be served
+/// - `Err(anyhow::Error)` - CORS origins, error pages, the favicon or the access log
+///   could not be loaded
+pub fn build_router(cfg: &AppConfig) -> Result<Router, anyhow::Error> {
+    build_router_with(cfg, |router| router)
+}
+
+/// Build the gateway router, letting embedders add their own routes and layers
+///
+/// `customize` receives the router with the gateway routes before any
+/// built-in middleware is applied. Layers added there run inside the rest of
+/// the stack (outermost first):
+///
+/// 1. CORS (preflight requests are answered here and never reach custom layers)
+/// 2. quiet logging tags, tracing span, client IP resolution, access log,
+///    request ID, HTML error pages
+/// 3. HTTP/1.0 Host handling, URI length, header and body limits
+/// 4. chaos fault injection (when `chaos_enabled`), panic recovery
+/// 5. custom layers, then the route handler
+///
+/// Custom layers therefore see the request ID and `ClientIp` in the request
+/// extensions (the latter when served with `ConnectInfo<SocketAddr>`), and
+/// their error responses still get the request ID header and error pages.
+///
+/// # Arguments
+/// - `cfg` - Validated application configuration
+/// - `customize` - Hook applied to the router before built-in middleware
+///
+/// # Returns
+/// - `Ok(Router)` - Router ready to be served
+/// - `Err(anyhow::Error)` - CORS origins, error pages, the favicon or the access log
+///   could not be loaded
+pub fn build_router_with<F>(cfg: &AppConfig, customize: F) -> Result<Router, anyhow::Error>
+where
+    F: FnOnce(Router) -> Router,
+{
+    // Load static HTML error pages into memory
+    let error_pages = ErrorPages::load(&cfg.error_pages)
+        .map_err(|e| anyhow::anyhow!("Failed to load error page: {}", e))?;
+
+    // Build HTTP router with middleware
+    let mut router = Router::new()
+        .route("/", get(root))
+        .route("/healthz", get(health))
+        .route(
+            "/slow",
+            get({
+                let timeout_duration = cfg.timeout_duration();
+                move || async move { with_timeout(timeout_duration, slow_endpoint()).await }
+            }),
+        );
+
+    // Favicon requests are answered locally and kept out of info-level logs
+    let mut log_exclude_paths = cfg.log_exclude_paths.clone();
+    if cfg.serve_favicon {
+        let icon = match &cfg.favicon_path {
+            Some(path) => {
+                let bytes = std::fs::read(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to load favicon ({}): {}", path, e))?;
+                Some((favicon_content_type(path), Bytes::from(bytes)))
+            }
+            None => None,
+        };
+        router = router.route("/favicon.ico", get(move || favicon(icon)));
+        log_exclude_paths.push("/favicon.ico".to_string());
+    }
+
+    // Embedder layers run inside all built-in middleware; panics in them or
+    // in handlers become a 500 envelope instead of a dropped connection
+    let mut app = customize(router).layer(tower_http::catch_panic::CatchPanicLayer::custom(
+        panic_response,
+    ));
+
+    // Injected faults go through the rest of the stack like real errors
+    if let Some(chaos) = cfg.chaos() {
+        tracing::warn!(
+            ?chaos,
+            "Chaos testing enabled: injecting latency and errors"
+        );
+        app = app.layer(axum::middleware::from_fn_with_state(
+            chaos,
+            chaos_middleware,
+        ));
+    }
+
+    // Error pages wrap the limit layers so their 4xx responses get HTML too
+    let mut app = app
+        .layer(axum::middleware::from_fn_with_state(
+            cfg.body_limits(),
+            body_limits_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            cfg.header_limits(),
+            header_limits_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            cfg.max_uri_length,
+            uri_length_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            cfg.http10_default_host(),
+            http10_host_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            std::sync::Arc::new(error_pages),
+            error_page_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            cfg.request_id_scheme,
+            request_id_middleware,
+        ));
+
+    // Access log lines are written after the request ID is on the response
+    if let Some(path) = &cfg.access_log_path {
+        let access_log = AccessLog::open(
+            path,
+            cfg.access_log_rotation,
+            cfg.access_log_max_bytes,
+            cfg.access_log_max_files,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to open access log ({}): {}", path, e))?;
+        app = app.layer(axum::middleware::from_fn_with_state(
+            access_log,
+            access_log_middleware,
+        ));
+    }
+
+    let app = app
+        .layer(axum::middleware::from_fn_with_state(
+            TrustedProxies::new(&cfg.trusted_proxies),
+            client_ip_middleware,
+        ))
+        .layer(
+            tower_http::trace::TraceLayer::new_for_http()
+                .make_span_with(RequestSpan)
+                // request_id_middleware logs request start once the ID is on the span
+                .on_request(())
+                .on_response(QuietOnResponse::new(
+                    DefaultOnResponse::new().level(tracing::Level::INFO),
+                ))
+                .on_failure(DefaultOnFailure::new().level(tracing::Level::ERROR)),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            LogExcludePaths::new(&log_exclude_paths),
+            quiet_log_middleware,
+        ))
+        .layer(ServiceBuilder::new().layer(cors_layer(cfg)?));
+
+    Ok(app)
+}
@@ -0,0 +1,173 @@
+use api_gateway::{
+    access_log::{AccessLogRotation, RotatingFile},
+    config::AppConfig,
+    router::build_router,
+};
+use axum::{body::Body, extract::Request, http::StatusCode};
+use tower::ServiceExt;
+
+/// Create a fresh temp directory for one test's log files
+fn log_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "api-gateway-access-log-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Wait for the access log writer thread to flush a line to `path`
+async fn wait_for_line(path: &std::path::Path) -> String {
+    for _ in 0..100 {
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        if contents.ends_with('\n') {
+            return contents;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    panic!("access log line was not written");
+}
+
+/// List rotated files next to `dir/access.log`
+fn rotated_files(dir: &std::path::Path) -> Vec<String> {
+    let mut rotated: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.starts_with("access.log."))
+        .collect();
+    rotated.sort();
+    rotated
+}
+
+/// Test that completed requests are written to the access log as JSON lines
+#[tokio::test]
+async fn test_requests_written_to_access_log() {
+    let path = log_dir("requests").join("access.log");
+    let mut cfg = AppConfig::load_from_file("nonexistent-access-log-test-config").unwrap();
+    cfg.access_log_path = Some(path.to_string_lossy().into_owned());
+    let app = build_router(&cfg).unwrap();
+
+    let request = Request::builder()
+        .uri("/?page=2")
+        .header("user-agent", "access-log-test")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let request_id = response
+        .headers()
+        .get("x-request-id")
+        .unwrap()
+        .to_str()
+        .unwrap();
+
+    // Lines are written by a background thread
+    let contents = wait_for_line(&path).await;
+    let lines: Vec<&str> = contents.lines().collect();
+    assert_eq!(lines.len(), 1);
+
+    let entry: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(entry["method"], "GET");
+    assert_eq!(entry["uri"], "/?page=2");
+    assert_eq!(entry["status"], 200);
+    assert_eq!(entry["request_id"], request_id);
+    assert_eq!(entry["user_agent"], "access-log-test");
+    assert!(entry["timestamp"].as_str().unwrap().ends_with('Z'));
+}
+
+/// Test that size rotation moves the full file aside before it overflows
+#[test]
+fn test_size_rotation() {
+    let dir = log_dir("size-rotation");
+    let path = dir.join("access.log");
+    let mut log = RotatingFile::open(&path, AccessLogRotation::Size, 100, 7).unwrap();
+
+    let line = [b'x'; 59];
+    log.write_line(&line).unwrap();
+    log.write_line(&line).unwrap();
+
+    // Active file holds only the second line; the first was rotated out
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 59);
+    assert_eq!(rotated_files(&dir).len(), 1);
+}
+
+/// Test that only the newest max_files rotated files are kept
+#[test]
+fn test_rotated_files_pruned() {
+    let dir = log_dir("retention");
+    let path = dir.join("access.log");
+    // Unrelated siblings sharing the prefix, older than every rotated file
+    for (age_days, suffix) in [(10, "keep"), (10, "gz"), (10, "2026-01-01.gz")] {
+        let other = std::fs::File::create(dir.join(format!("access.log.{suffix}"))).unwrap();
+        other
+            .set_modified(
+                std::time::SystemTime::now() - std::time::Duration::from_secs(age_days * 86_400),
+            )
+            .unwrap();
+    }
+    for (age_days, day) in [(3, "2026-01-01"), (2, "2026-01-02"), (1, "2026-01-03")] {
+        let old = std::fs::File::create(dir.join(format!("access.log.{day}"))).unwrap();
+        old.set_modified(
+            std::time::SystemTime::now() - std::time::Duration::from_secs(age_days * 86_400),
+        )
+        .unwrap();
+    }
+    let mut log = RotatingFile::open(&path, AccessLogRotation::Size, 100, 2).unwrap();
+
+    let line = [b'x'; 59];
+    log.write_line(&line).unwrap();
+    log.write_line(&line).unwrap();
+
+    // The fresh rotation and the newest existing file survive
+    let rotated = rotated_files(&dir);
+    assert_eq!(rotated.len(), 5);
+    for other in [
+        "access.log.keep",
+        "access.log.gz",
+        "access.log.2026-01-01.gz",
+    ] {
+        assert!(rotated.contains(&other.to_string()), "{other} was pruned");
+    }
+    assert!(rotated.contains(&"access.log.2026-01-03".to_string()));
+    assert!(!rotated.contains(&"access.log.2026-01-01".to_string()));
+    assert!(!rotated.contains(&"access.log.2026-01-02".to_string()));
+}
+
+/// Test that a file last written on an earlier day is rotated, not appended to
+#[test]
+fn test_daily_rotation_of_stale_file() {
+    let dir = log_dir("stale-daily");
+    let path = dir.join("access.log");
+    let yesterday = std::time::SystemTime::now() - std::time::Duration::from_secs(86_400);
+    let stale = std::fs::File::create(&path).unwrap();
+    std::io::Write::write_all(&mut &stale, b"yesterday\n").unwrap();
+    stale.set_modified(yesterday).unwrap();
+    drop(stale);
+
+    let mut log = RotatingFile::open(&path, AccessLogRotation::Daily, 1024, 7).unwrap();
+    log.write_line(b"today\n").unwrap();
+
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "today\n");
+    let rotated = rotated_files(&dir);
+    assert_eq!(rotated.len(), 1);
+    assert_eq!(
+        std::fs::read_to_string(dir.join(&rotated[0])).unwrap(),
+        "yesterday\n"
+    );
+}
+
+/// Test that rotation "never" keeps appending to one file
+#[test]
+fn test_never_rotation_appends() {
+    let path = log_dir("never-rotation").join("access.log");
+    let mut log = RotatingFile::open(&path, AccessLogRotation::Never, 1024, 7).unwrap();
+
+    for _ in 0..3 {
+        log.write_line(&[b'x'; 1000]).unwrap();
+    }
+
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 3000);
+}
@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+use api_gateway::{config::AppConfig, router::build_router};
+use axum::{body::Body, extract::Request, http::StatusCode, response::Response};
+use tower::ServiceExt;
+
+/// Load the default config with the given chaos settings
+fn chaos_config(enabled: bool, latency_ms: u64, latency_p: f64, error_p: f64) -> AppConfig {
+    let mut cfg = AppConfig::load_from_file("nonexistent-chaos-test-config").unwrap();
+    cfg.chaos_enabled = enabled;
+    cfg.chaos_latency_ms = latency_ms;
+    cfg.chaos_latency_probability = latency_p;
+    cfg.chaos_error_probability = error_p;
+    cfg
+}
+
+/// Send a GET request for `uri` through the full gateway router
+async fn get(cfg: &AppConfig, uri: &str) -> Response {
+    let app = build_router(cfg).unwrap();
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    app.oneshot(request).await.unwrap()
+}
+
+/// Test that an error probability of 1.0 turns every request into a 503 envelope
+#[tokio::test]
+async fn test_chaos_injects_errors() {
+    let cfg = chaos_config(true, 0, 0.0, 1.0);
+
+    let response = get(&cfg, "/").await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(response.headers().get("x-request-id").is_some());
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], 503);
+}
+
+/// Test that a latency probability of 1.0 delays every request
+#[tokio::test]
+async fn test_chaos_injects_latency() {
+    let cfg = chaos_config(true, 100, 1.0, 0.0);
+
+    let started = Instant::now();
+    let response = get(&cfg, "/").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+}
+
+/// Test that chaos settings have no effect unless chaos_enabled is set
+#[tokio::test]
+async fn test_chaos_disabled_ignores_settings() {
+    let cfg = chaos_config(false, 0, 0.0, 1.0);
+
+    let response = get(&cfg, "/").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Test that health checks are exempt from fault injection
+#[tokio::test]
+async fn test_chaos_skips_health_checks() {
+    let cfg = chaos_config(true, 0, 0.0, 1.0);
+
+    let response = get(&cfg, "/healthz").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,127 @@
+use std::net::{IpAddr, SocketAddr};
+
+use api_gateway::client_ip::{client_ip, client_ip_middleware, ClientIp, TrustedProxies};
+use axum::{
+    body::Body,
+    extract::{connect_info::MockConnectInfo, Request},
+    http::{HeaderMap, HeaderValue},
+    routing::get,
+    Extension, Router,
+};
+use tower::ServiceExt;
+
+/// Trusted proxies used by these tests: a load balancer subnet and one edge proxy
+fn trusted() -> TrustedProxies {
+    TrustedProxies::new(&[
+        "10.0.0.0/8".parse().unwrap(),
+        "192.0.2.1/32".parse().unwrap(),
+    ])
+}
+
+/// Build a header map with the given X-Forwarded-For value
+fn xff(value: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+    headers
+}
+
+/// Parse an IP address literal
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+/// Test that XFF is ignored when the direct peer is not a trusted proxy
+#[test]
+fn test_untrusted_peer_ignores_xff() {
+    let resolved = client_ip(ip("203.0.113.9"), &xff("1.2.3.4"), &trusted());
+    assert_eq!(resolved, ip("203.0.113.9"));
+}
+
+/// Test that the first untrusted address from the right is the client
+#[test]
+fn test_walks_chain_right_to_left() {
+    let resolved = client_ip(
+        ip("10.0.0.5"),
+        &xff("198.51.100.7, 192.0.2.1, 10.1.2.3"),
+        &trusted(),
+    );
+    assert_eq!(resolved, ip("198.51.100.7"));
+}
+
+/// Test that client-supplied entries left of the real client are not trusted
+#[test]
+fn test_spoofed_leftmost_entry_ignored() {
+    // Client sent "X-Forwarded-For: 127.0.0.1", the proxy appended its real address
+    let resolved = client_ip(ip("10.0.0.5"), &xff("127.0.0.1, 198.51.100.7"), &trusted());
+    assert_eq!(resolved, ip("198.51.100.7"));
+}
+
+/// Test that a spoofed trusted-looking address cannot hide the real client
+#[test]
+fn test_spoofed_trusted_address_ignored() {
+    let resolved = client_ip(ip("10.0.0.5"), &xff("10.9.9.9, 198.51.100.7"), &trusted());
+    assert_eq!(resolved, ip("198.51.100.7"));
+}
+
+/// Test that repeated XFF header fields are read as one chain in order
+#[test]
+fn test_multiple_xff_fields() {
+    let mut headers = xff("198.51.100.7");
+    headers.append("x-forwarded-for", HeaderValue::from_static("192.0.2.1"));
+
+    let resolved = client_ip(ip("10.0.0.5"), &headers, &trusted());
+    assert_eq!(resolved, ip("198.51.100.7"));
+}
+
+/// Test that an all-trusted chain resolves to its leftmost address
+#[test]
+fn test_all_trusted_chain_uses_leftmost() {
+    let resolved = client_ip(ip("10.0.0.5"), &xff("10.0.0.1, 10.0.0.2"), &trusted());
+    assert_eq!(resolved, ip("10.0.0.1"));
+}
+
+/// Test that garbage in the chain stops the walk at the last trusted hop
+#[test]
+fn test_unparsable_entry_stops_walk() {
+    let resolved = client_ip(
+        ip("10.0.0.5"),
+        &xff("198.51.100.7, not-an-ip, 10.0.0.2"),
+        &trusted(),
+    );
+    assert_eq!(resolved, ip("10.0.0.2"));
+}
+
+/// Test that a trusted peer without XFF is the client itself
+#[test]
+fn test_trusted_peer_without_xff() {
+    let resolved = client_ip(ip("10.0.0.5"), &HeaderMap::new(), &trusted());
+    assert_eq!(resolved, ip("10.0.0.5"));
+}
+
+/// Test that the middleware stores the resolved ClientIp in request extensions
+#[tokio::test]
+async fn test_middleware_inserts_client_ip() {
+    let app = Router::new()
+        .route(
+            "/",
+            get(|Extension(ClientIp(ip)): Extension<ClientIp>| async move { ip.to_string() }),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            trusted(),
+            client_ip_middleware,
+        ))
+        .layer(MockConnectInfo(SocketAddr::from(([10, 0, 0, 5], 40000))));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-forwarded-for", "203.0.113.50")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    assert_eq!(&body[..], b"203.0.113.50");
+}
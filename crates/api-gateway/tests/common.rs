@@ -29,7 +29,8 @@ pub fn create_test_app() -> Router {
     Router::new()
         .route("/", get(root))
         .route("/healthz", get(health))
-        .layer(axum::middleware::from_fn(
+        .layer(axum::middleware::from_fn_with_state(
+            api_gateway::RequestIdScheme::default(),
             api_gateway::request_id_middleware,
         ))
         .layer(ServiceBuilder::new().layer(cors_layer))
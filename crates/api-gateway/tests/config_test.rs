@@ -0,0 +1,254 @@
+use api_gateway::config::{AppConfig, ConfigError};
+
+/// Write a config file to a unique temp path and return the path
+fn write_config(name: &str, contents: &str) -> String {
+    let path =
+        std::env::temp_dir().join(format!("api-gateway-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+/// Test that upstream maps larger than max_upstreams are rejected
+#[test]
+fn test_too_many_upstreams_rejected() {
+    let path = write_config(
+        "too-many-upstreams",
+        r#"
+max_upstreams = 2
+
+[upstreams]
+a = "http://localhost:3001"
+b = "http://localhost:3002"
+c = "http://localhost:3003"
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::TooManyUpstreams(3, 2))),
+        "Expected TooManyUpstreams error, got: {:?}",
+        result
+    );
+}
+
+/// Test that upstream maps within max_upstreams are accepted
+#[test]
+fn test_upstreams_within_limit_accepted() {
+    let path = write_config(
+        "upstreams-within-limit",
+        r#"
+max_upstreams = 2
+
+[upstreams]
+a = "http://localhost:3001"
+b = "http://localhost:3002"
+"#,
+    );
+
+    let cfg = AppConfig::load_from_file(&path).unwrap();
+    assert_eq!(cfg.upstreams.len(), 2);
+    assert_eq!(cfg.max_upstreams, 2);
+}
+
+/// Test that upstream URLs are stored parsed and serialize back as strings
+#[test]
+fn test_upstreams_are_parsed_urls() {
+    let path = write_config(
+        "parsed-upstreams",
+        r#"
+[upstreams]
+video_service = "http://localhost:3003/api"
+"#,
+    );
+
+    let cfg = AppConfig::load_from_file(&path).unwrap();
+
+    let url = cfg.get_upstream("video_service").unwrap();
+    assert_eq!(url.scheme(), "http");
+    assert_eq!(url.host_str(), Some("localhost"));
+    assert_eq!(url.port(), Some(3003));
+    assert_eq!(url.path(), "/api");
+    assert!(cfg.get_upstream("missing_service").is_none());
+
+    let json = serde_json::to_value(&cfg).unwrap();
+    assert_eq!(
+        json["upstreams"]["video_service"],
+        "http://localhost:3003/api"
+    );
+}
+
+/// Test that a bare-origin upstream is normalized with a trailing slash
+#[test]
+#[allow(deprecated)]
+fn test_upstream_without_path_normalized() {
+    let path = write_config(
+        "bare-origin-upstream",
+        r#"
+[upstreams]
+video_service = "http://localhost:3003"
+"#,
+    );
+
+    let cfg = AppConfig::load_from_file(&path).unwrap();
+
+    assert_eq!(cfg.get_upstream("video_service").unwrap().path(), "/");
+    assert_eq!(
+        cfg.get_upstream_url("video_service"),
+        Some("http://localhost:3003/")
+    );
+
+    let json = serde_json::to_value(&cfg).unwrap();
+    assert_eq!(json["upstreams"]["video_service"], "http://localhost:3003/");
+}
+
+/// Test that upstreams with a non-http scheme are rejected
+#[test]
+fn test_upstream_invalid_scheme_rejected() {
+    let path = write_config(
+        "invalid-scheme-upstream",
+        r#"
+[upstreams]
+video_service = "ftp://localhost:3003"
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidUpstreamUrl(ref name, _)) if name == "video_service"),
+        "Expected InvalidUpstreamUrl error, got: {:?}",
+        result
+    );
+}
+
+/// Test that a favicon_path pointing at a missing file is rejected
+#[test]
+fn test_missing_favicon_path_rejected() {
+    let path = write_config(
+        "missing-favicon",
+        r#"
+serve_favicon = true
+favicon_path = "/nonexistent/favicon.ico"
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidFaviconPath(_))),
+        "Expected InvalidFaviconPath error, got: {:?}",
+        result
+    );
+}
+
+/// Test that socket buffer sizes outside the sane range are rejected
+#[test]
+fn test_socket_buffer_out_of_range_rejected() {
+    let path = write_config(
+        "socket-buffer-range",
+        r#"
+socket_send_buffer_bytes = 1024
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidSocketBuffer(ref name, 1024)) if name == "socket_send_buffer_bytes"),
+        "Expected InvalidSocketBuffer error, got: {:?}",
+        result
+    );
+}
+
+/// Test that trusted proxies accept CIDRs and bare IPs
+#[test]
+fn test_trusted_proxies_parsed() {
+    let path = write_config(
+        "trusted-proxies",
+        r#"
+trusted_proxies = ["10.0.0.0/8", "192.0.2.1", "2001:db8::/32"]
+"#,
+    );
+
+    let cfg = AppConfig::load_from_file(&path).unwrap();
+    assert_eq!(cfg.trusted_proxies.len(), 3);
+    assert_eq!(cfg.trusted_proxies[1].to_string(), "192.0.2.1/32");
+}
+
+/// Test that malformed trusted proxy entries are rejected
+#[test]
+fn test_invalid_trusted_proxy_rejected() {
+    let path = write_config(
+        "invalid-trusted-proxy",
+        r#"
+trusted_proxies = ["10.0.0.0/33"]
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidTrustedProxy(ref entry)) if entry == "10.0.0.0/33"),
+        "Expected InvalidTrustedProxy error, got: {:?}",
+        result
+    );
+}
+
+/// Test that a zero connection limit is rejected
+#[test]
+fn test_zero_max_connections_rejected() {
+    let path = write_config("zero-max-connections", "max_connections = 0\n");
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidMaxConnections(0))),
+        "Expected InvalidMaxConnections error, got: {:?}",
+        result
+    );
+}
+
+/// Test that chaos probabilities outside 0.0-1.0 are rejected
+#[test]
+fn test_chaos_probability_out_of_range_rejected() {
+    let path = write_config("chaos-probability", "chaos_error_probability = 1.5\n");
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidChaos(_))),
+        "Expected InvalidChaos error, got: {:?}",
+        result
+    );
+}
+
+/// Test that CORS credentials combined with a wildcard origin are rejected
+#[test]
+fn test_cors_credentials_with_wildcard_rejected() {
+    let path = write_config(
+        "cors-credentials-wildcard",
+        r#"
+cors_origins = ["*"]
+cors_allow_credentials = true
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidCorsOrigin(_))),
+        "Expected InvalidCorsOrigin error, got: {:?}",
+        result
+    );
+}
+
+/// Test that an http10_default_host that isn't a host is rejected
+#[test]
+fn test_invalid_http10_default_host_rejected() {
+    let path = write_config(
+        "invalid-http10-host",
+        r#"
+http10_default_host = "http://example.com/path"
+"#,
+    );
+
+    let result = AppConfig::load_from_file(&path);
+    assert!(
+        matches!(result, Err(ConfigError::InvalidHttp10DefaultHost(_))),
+        "Expected InvalidHttp10DefaultHost error, got: {:?}",
+        result
+    );
+}
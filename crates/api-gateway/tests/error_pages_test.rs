@@ -0,0 +1,165 @@
+use std::{collections::HashMap, sync::Arc};
+
+use api_gateway::{
+    config::AppConfig,
+    error_pages::{error_page_middleware, prefers_html, ErrorPages},
+    router::build_router,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use serde_json::json;
+use tower::ServiceExt;
+
+/// Handler returning the standard JSON error envelope
+async fn failing() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::BAD_GATEWAY,
+        Json(json!({
+            "error": "Bad Gateway",
+            "message": "Upstream failed",
+            "status": 502
+        })),
+    )
+}
+
+/// Write an error page to a unique temp file and return its path
+fn write_page(name: &str, html: &str) -> String {
+    let path =
+        std::env::temp_dir().join(format!("api-gateway-{}-{}.html", name, std::process::id()));
+    std::fs::write(&path, html).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+/// Create a test app with a 502 error page configured
+fn create_app(name: &str) -> Router {
+    let path = write_page(name, "<h1>Something went wrong</h1>");
+    let pages = ErrorPages::load(&HashMap::from([(502, path)])).unwrap();
+
+    Router::new()
+        .route("/fail", get(failing))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(pages),
+            error_page_middleware,
+        ))
+}
+
+/// Test that browsers get the configured HTML page
+#[tokio::test]
+async fn test_html_client_gets_error_page() {
+    let app = create_app("html-client");
+
+    let request = Request::builder()
+        .uri("/fail")
+        .header(
+            "accept",
+            "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+        )
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    // Status code is preserved
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+
+    let content_type = response.headers().get("content-type").unwrap();
+    assert!(content_type.to_str().unwrap().starts_with("text/html"));
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"<h1>Something went wrong</h1>");
+}
+
+/// Test that API clients keep receiving the JSON envelope
+#[tokio::test]
+async fn test_json_client_gets_envelope() {
+    let app = create_app("json-client");
+
+    let request = Request::builder()
+        .uri("/fail")
+        .header("accept", "application/json")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], 502);
+}
+
+/// Test that statuses without a configured page are left untouched
+#[tokio::test]
+async fn test_unconfigured_status_passes_through() {
+    let app = create_app("unconfigured");
+
+    let request = Request::builder()
+        .uri("/missing")
+        .header("accept", "text/html")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().get("content-type").is_none());
+}
+
+/// Test that responses generated by the gateway's own limit layers get pages too
+#[tokio::test]
+async fn test_limit_rejection_gets_error_page() {
+    let path = write_page("payload-too-large", "<h1>Upload too large</h1>");
+    let mut cfg = AppConfig::load_from_file("nonexistent-error-pages-test-config").unwrap();
+    cfg.error_pages = HashMap::from([(413, path)]);
+    cfg.max_body_bytes = 16;
+    let app = build_router(&cfg).unwrap();
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("accept", "text/html")
+        .header("content-length", "1024")
+        .body(Body::from(vec![b'x'; 1024]))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(response.headers().get("x-request-id").is_some());
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"<h1>Upload too large</h1>");
+}
+
+/// Test Accept header preference parsing
+#[test]
+fn test_prefers_html() {
+    assert!(prefers_html("text/html"));
+    assert!(prefers_html("text/html,application/xhtml+xml,*/*;q=0.8"));
+    assert!(prefers_html("application/json;q=0.5, text/html"));
+    assert!(!prefers_html("*/*"));
+    assert!(!prefers_html("application/json"));
+    assert!(!prefers_html("text/html;q=0.5, application/json"));
+    assert!(!prefers_html("text/html;q=0"));
+}
+
+/// Test that a missing error page file fails at startup
+#[test]
+fn test_missing_error_page_fails_to_load() {
+    let result = ErrorPages::load(&HashMap::from([(
+        500,
+        "/nonexistent/api-gateway/500.html".to_string(),
+    )]));
+    assert!(result.is_err());
+}
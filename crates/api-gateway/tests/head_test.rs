@@ -0,0 +1,55 @@
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use tower::ServiceExt;
+
+mod common;
+
+/// Test that HEAD on the health endpoint succeeds with an empty body
+#[tokio::test]
+async fn test_head_health_returns_empty_body() {
+    let app = common::create_test_app();
+
+    let request = Request::builder()
+        .method(Method::HEAD)
+        .uri("/healthz")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    // Should return 200 OK instead of 405
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Should still carry the request ID
+    assert!(response.headers().get("x-request-id").is_some());
+
+    // Should have an empty body
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty(), "HEAD response body should be empty");
+}
+
+/// Test that HEAD on the root endpoint mirrors GET headers
+#[tokio::test]
+async fn test_head_root_matches_get_headers() {
+    let app = common::create_test_app();
+
+    let get_request = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let head_request = Request::builder()
+        .method(Method::HEAD)
+        .uri("/")
+        .body(Body::empty())
+        .unwrap();
+
+    let get_response = app.clone().oneshot(get_request).await.unwrap();
+    let head_response = app.oneshot(head_request).await.unwrap();
+
+    assert_eq!(head_response.status(), StatusCode::OK);
+    assert_eq!(
+        head_response.headers().get("content-type"),
+        get_response.headers().get("content-type")
+    );
+}
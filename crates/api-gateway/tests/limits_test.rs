@@ -0,0 +1,325 @@
+use api_gateway::{
+    config::AppConfig,
+    limits::{
+        body_limits_middleware, header_limits_middleware, http10_host_middleware,
+        uri_length_middleware, BodyLimits, HeaderLimits,
+    },
+    router::build_router,
+};
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderValue, Request, StatusCode, Version},
+    routing::{get, post},
+    Router,
+};
+use tower::ServiceExt;
+
+/// Root endpoint for testing
+async fn root() -> &'static str {
+    "api gateway: okay"
+}
+
+/// Create a test app with small header limits
+fn create_app() -> Router {
+    Router::new()
+        .route("/", get(root))
+        .layer(axum::middleware::from_fn_with_state(
+            HeaderLimits {
+                max_count: 5,
+                max_bytes: 256,
+            },
+            header_limits_middleware,
+        ))
+}
+
+/// Read the JSON error envelope from a response
+async fn envelope(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+/// Test that requests within the limits pass through
+#[tokio::test]
+async fn test_headers_within_limits_pass() {
+    let request = Request::builder()
+        .uri("/")
+        .header("x-one", "1")
+        .header("x-two", "2")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = create_app().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Test that too many headers are rejected with 431
+#[tokio::test]
+async fn test_too_many_headers_rejected() {
+    let mut builder = Request::builder().uri("/");
+    for i in 0..6 {
+        builder = builder.header(format!("x-header-{}", i), "v");
+    }
+    let request = builder.body(Body::empty()).unwrap();
+
+    let response = create_app().oneshot(request).await.unwrap();
+
+    assert_eq!(
+        response.status(),
+        StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+    );
+    let json = envelope(response).await;
+    assert_eq!(json["status"], 431);
+    assert_eq!(json["error"], "Request Header Fields Too Large");
+}
+
+/// Test that oversized header values are rejected with 431
+#[tokio::test]
+async fn test_oversized_headers_rejected() {
+    let request = Request::builder()
+        .uri("/")
+        .header("x-big", "a".repeat(300))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = create_app().oneshot(request).await.unwrap();
+
+    assert_eq!(
+        response.status(),
+        StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE
+    );
+    let json = envelope(response).await;
+    assert_eq!(json["status"], 431);
+}
+
+/// Create a test app with a small URI length limit
+fn create_uri_app() -> Router {
+    Router::new()
+        .route("/", get(root))
+        .layer(axum::middleware::from_fn_with_state(
+            64,
+            uri_length_middleware,
+        ))
+}
+
+/// Test that URIs within the limit pass through
+#[tokio::test]
+async fn test_uri_within_limit_passes() {
+    let request = Request::builder()
+        .uri("/?q=short")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = create_uri_app().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+/// Test that over-length URIs are rejected with 414
+#[tokio::test]
+async fn test_over_length_uri_rejected() {
+    let request = Request::builder()
+        .uri(format!("/?q={}", "a".repeat(100)))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = create_uri_app().oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::URI_TOO_LONG);
+    let json = envelope(response).await;
+    assert_eq!(json["status"], 414);
+    assert_eq!(json["error"], "URI Too Long");
+}
+
+/// Echo the received body length
+async fn body_length(body: axum::body::Bytes) -> String {
+    body.len().to_string()
+}
+
+/// Create a test app with a 16 byte body limit
+fn create_body_app(strict_length: bool) -> Router {
+    Router::new()
+        .route("/", post(body_length))
+        .layer(axum::middleware::from_fn_with_state(
+            BodyLimits {
+                max_bytes: 16,
+                strict_length,
+                timeout: std::time::Duration::from_secs(5),
+            },
+            body_limits_middleware,
+        ))
+}
+
+/// Test that a declared Content-Length over the limit is rejected with 413
+#[tokio::test]
+async fn test_oversized_content_length_rejected() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-length", "1000000")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = create_body_app(false).oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let json = envelope(response).await;
+    assert_eq!(json["status"], 413);
+}
+
+/// Test that bodies within the limit pass through unchanged
+#[tokio::test]
+async fn test_body_within_limit_passes() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-length", "5")
+        .body(Body::from("hello"))
+        .unwrap();
+
+    let response = create_body_app(true).oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"5");
+}
+
+/// Test that a body shorter than its Content-Length is rejected with 400 in strict mode
+#[tokio::test]
+async fn test_content_length_mismatch_rejected() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-length", "10")
+        .body(Body::from("abc"))
+        .unwrap();
+
+    let response = create_body_app(true).oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let json = envelope(response).await;
+    assert_eq!(
+        json["message"],
+        "Request body does not match Content-Length"
+    );
+}
+
+/// Test that a client stalling mid-body gets 408 from the strict length check
+#[tokio::test]
+async fn test_stalled_body_in_strict_mode_returns_408() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut cfg = AppConfig::load_from_file("nonexistent-limits-test-config").unwrap();
+    cfg.strict_content_length = true;
+    cfg.client_body_timeout_ms = 100;
+    let app = build_router(&cfg).unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    // Promise 10 bytes, send 2, then stall
+    let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    client
+        .write_all(b"POST / HTTP/1.1\r\nhost: localhost\r\ncontent-length: 10\r\n\r\nab")
+        .await
+        .unwrap();
+
+    let mut response = vec![0u8; 1024];
+    let read = tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        client.read(&mut response),
+    )
+    .await
+    .expect("gateway should answer a stalled body")
+    .unwrap();
+    let response = String::from_utf8_lossy(&response[..read]);
+
+    assert!(response.starts_with("HTTP/1.1 408"), "got: {response}");
+}
+
+/// Test that an unparsable Content-Length is rejected with 400
+#[tokio::test]
+async fn test_invalid_content_length_rejected() {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/")
+        .header("content-length", "ten")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = create_body_app(false).oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+/// Echo the Host header the handler received
+async fn echo_host(headers: HeaderMap) -> String {
+    headers
+        .get("host")
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("none")
+        .to_string()
+}
+
+/// Create a test app with an optional HTTP/1.0 default host
+fn create_http10_app(default_host: Option<&'static str>) -> Router {
+    Router::new()
+        .route("/", get(echo_host))
+        .layer(axum::middleware::from_fn_with_state(
+            default_host.map(HeaderValue::from_static),
+            http10_host_middleware,
+        ))
+}
+
+/// Build an HTTP/1.0 request without a Host header
+fn http10_request() -> Request<Body> {
+    Request::builder()
+        .uri("/")
+        .version(Version::HTTP_10)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Test that HTTP/1.0 requests without Host are rejected with 400 and closed
+#[tokio::test]
+async fn test_http10_missing_host_rejected() {
+    let response = create_http10_app(None)
+        .oneshot(http10_request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers().get("connection").unwrap(), "close");
+    let json = envelope(response).await;
+    assert_eq!(json["status"], 400);
+}
+
+/// Test that the configured default host is used for HTTP/1.0 requests without Host
+#[tokio::test]
+async fn test_http10_default_host_applied() {
+    let response = create_http10_app(Some("videos.example.com"))
+        .oneshot(http10_request())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"videos.example.com");
+}
+
+/// Test that HTTP/1.1 requests are not affected
+#[tokio::test]
+async fn test_http11_without_host_untouched() {
+    let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+
+    let response = create_http10_app(None).oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use api_gateway::listener::{applied_buffer_sizes, bind, ConnectionLimit, SocketBuffers};
+use axum::serve::Listener;
+use socket2::SockRef;
+
+/// Test that requested socket buffer sizes are applied to the listener
+#[tokio::test]
+async fn test_socket_buffers_applied_to_listener() {
+    let buffers = SocketBuffers {
+        recv_bytes: Some(65536),
+        send_bytes: Some(65536),
+    };
+
+    let listener = bind("127.0.0.1:0", buffers).await.unwrap();
+    let (recv, send) = applied_buffer_sizes(&listener).unwrap();
+
+    // The kernel may round up (Linux doubles the value) but never goes below
+    assert!(recv >= 65536, "receive buffer was {recv}");
+    assert!(send >= 65536, "send buffer was {send}");
+}
+
+/// Test that accepted connections inherit the listener's buffer sizes
+#[tokio::test]
+async fn test_accepted_connections_inherit_buffers() {
+    let buffers = SocketBuffers {
+        recv_bytes: Some(131072),
+        send_bytes: None,
+    };
+
+    let listener = bind("127.0.0.1:0", buffers).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let _client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (stream, _) = listener.accept().await.unwrap();
+
+    let recv = SockRef::from(&stream).recv_buffer_size().unwrap();
+    assert!(recv >= 131072, "receive buffer was {recv}");
+}
+
+/// Test that binding without buffer settings keeps the OS defaults
+#[tokio::test]
+async fn test_default_buffers_bind() {
+    let listener = bind("127.0.0.1:0", SocketBuffers::default()).await.unwrap();
+    let (recv, send) = applied_buffer_sizes(&listener).unwrap();
+
+    assert!(recv > 0);
+    assert!(send > 0);
+}
+
+/// Test that accepts pause at max_connections and resume when a connection closes
+#[tokio::test]
+async fn test_connection_limit_pauses_accepts() {
+    let listener = bind("127.0.0.1:0", SocketBuffers::default()).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut limited = ConnectionLimit::new(listener, Some(1));
+
+    let _first_client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (first, _) = limited.accept().await;
+    assert_eq!(limited.active_connections(), 1);
+
+    // Second client waits in the backlog while the first connection is open
+    let _second_client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let blocked = tokio::time::timeout(Duration::from_millis(100), limited.accept()).await;
+    assert!(
+        blocked.is_err(),
+        "accept should pause at the connection limit"
+    );
+
+    // Closing the first connection frees its slot
+    drop(first);
+    let accepted = tokio::time::timeout(Duration::from_secs(2), limited.accept()).await;
+    assert!(
+        accepted.is_ok(),
+        "accept should resume once a connection closes"
+    );
+    assert_eq!(limited.active_connections(), 1);
+}
+
+/// Test that an unlimited listener accepts without tracking connections
+#[tokio::test]
+async fn test_connection_limit_unlimited() {
+    let listener = bind("127.0.0.1:0", SocketBuffers::default()).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut limited = ConnectionLimit::new(listener, None);
+
+    let _a = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let _b = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let _first = limited.accept().await;
+    let _second = limited.accept().await;
+
+    assert_eq!(limited.active_connections(), 0);
+}
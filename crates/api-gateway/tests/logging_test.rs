@@ -0,0 +1,152 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use api_gateway::{
+    logging::{quiet_log_middleware, LogExcludePaths, QuietOnResponse, RequestSpan},
+    request_id_middleware, RequestIdScheme,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::{layer::Context, layer::SubscriberExt, Layer};
+
+/// Tracing layer that records the level of every emitted event
+#[derive(Clone, Default)]
+struct CaptureLayer {
+    levels: Arc<Mutex<Vec<Level>>>,
+}
+
+impl<S: Subscriber> Layer<S> for CaptureLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        self.levels.lock().unwrap().push(*event.metadata().level());
+    }
+}
+
+impl CaptureLayer {
+    fn info_count(&self) -> usize {
+        self.levels
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|level| **level == Level::INFO)
+            .count()
+    }
+}
+
+/// Health check endpoint for testing
+async fn health() -> &'static str {
+    "ok"
+}
+
+/// Create a test app with the quiet logging stack used by the main app
+fn create_app() -> Router {
+    Router::new()
+        .route("/", get(health))
+        .route("/healthz", get(health))
+        .layer(axum::middleware::from_fn_with_state(
+            RequestIdScheme::default(),
+            request_id_middleware,
+        ))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(RequestSpan)
+                .on_request(())
+                .on_response(QuietOnResponse::new(
+                    DefaultOnResponse::new().level(Level::INFO),
+                )),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            LogExcludePaths::new(&["/healthz".to_string()]),
+            quiet_log_middleware,
+        ))
+}
+
+/// Send a GET request for `uri` with a capture layer installed
+async fn capture_info_logs(uri: &str) -> (StatusCode, bool, usize) {
+    let capture = CaptureLayer::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = create_app().oneshot(request).await.unwrap();
+
+    let has_request_id = response.headers().get("x-request-id").is_some();
+    (response.status(), has_request_id, capture.info_count())
+}
+
+/// Test that excluded paths emit no info-level logs but still get a request ID
+#[tokio::test]
+async fn test_excluded_path_emits_no_info_logs() {
+    let (status, has_request_id, info_count) = capture_info_logs("/healthz").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        has_request_id,
+        "Excluded path should still get a request ID"
+    );
+    assert_eq!(info_count, 0, "Excluded path should not emit info logs");
+}
+
+/// Test that other paths keep their info-level logs
+#[tokio::test]
+async fn test_other_paths_emit_info_logs() {
+    let (status, has_request_id, info_count) = capture_info_logs("/").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(has_request_id);
+    assert!(info_count > 0, "Non-excluded path should emit info logs");
+}
+
+/// Writer that appends formatted log output to a shared buffer
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Test that logs emitted while handling a request carry its request ID
+#[tokio::test]
+async fn test_request_logs_include_request_id_span_field() {
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(move || writer.clone()),
+    );
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-request-id", "span-field-test-id")
+        .body(Body::empty())
+        .unwrap();
+    let response = create_app().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(!lines.is_empty(), "Expected log output");
+    for line in lines {
+        assert!(
+            line.contains("request_id=span-field-test-id"),
+            "Log line should carry the request ID: {}",
+            line
+        );
+    }
+}
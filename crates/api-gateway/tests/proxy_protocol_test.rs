@@ -0,0 +1,288 @@
+use std::{net::SocketAddr, time::Duration};
+
+use api_gateway::{
+    listener::ConnectionLimit,
+    proxy_protocol::{
+        parse_header, ParseOutcome, ProxyProtocolError, ProxyProtocolListener, ProxyProtocolMode,
+    },
+};
+use axum::{extract::ConnectInfo, routing::get, serve::ListenerExt, Router};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Build a v2 PROXY header for a TCP over IPv4 connection
+fn v2_inet_header(src: [u8; 4], sport: u16) -> Vec<u8> {
+    let mut header = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+    header.extend_from_slice(&[0x21, 0x11, 0x00, 12]);
+    header.extend_from_slice(&src);
+    header.extend_from_slice(&[10, 0, 0, 1]);
+    header.extend_from_slice(&sport.to_be_bytes());
+    header.extend_from_slice(&443u16.to_be_bytes());
+    header
+}
+
+/// Test that a v1 TCP4 header yields the source address and header length
+#[test]
+fn test_parse_v1_tcp4() {
+    let header = b"PROXY TCP4 203.0.113.7 10.0.0.1 51234 443\r\nGET / HTTP/1.1\r\n";
+
+    let outcome = parse_header(header).unwrap();
+
+    assert_eq!(
+        outcome,
+        ParseOutcome::Header {
+            source: Some("203.0.113.7:51234".parse().unwrap()),
+            len: 43,
+        }
+    );
+}
+
+/// Test that a v1 TCP6 header is parsed
+#[test]
+fn test_parse_v1_tcp6() {
+    let header = b"PROXY TCP6 2001:db8::1 2001:db8::2 4000 443\r\n";
+
+    let outcome = parse_header(header).unwrap();
+
+    assert!(matches!(
+        outcome,
+        ParseOutcome::Header { source: Some(addr), .. } if addr == "[2001:db8::1]:4000".parse().unwrap()
+    ));
+}
+
+/// Test that a v1 UNKNOWN header carries no source address
+#[test]
+fn test_parse_v1_unknown() {
+    let outcome = parse_header(b"PROXY UNKNOWN\r\n").unwrap();
+    assert_eq!(
+        outcome,
+        ParseOutcome::Header {
+            source: None,
+            len: 15
+        }
+    );
+}
+
+/// Test that partial headers ask for more bytes
+#[test]
+fn test_parse_incomplete() {
+    assert_eq!(parse_header(b"").unwrap(), ParseOutcome::Incomplete);
+    assert_eq!(parse_header(b"PRO").unwrap(), ParseOutcome::Incomplete);
+    assert_eq!(
+        parse_header(b"PROXY TCP4 1.2.3.4").unwrap(),
+        ParseOutcome::Incomplete
+    );
+    assert_eq!(
+        parse_header(b"\r\n\r\n\0").unwrap(),
+        ParseOutcome::Incomplete
+    );
+    assert_eq!(
+        parse_header(&v2_inet_header([1, 2, 3, 4], 1)[..20]).unwrap(),
+        ParseOutcome::Incomplete
+    );
+}
+
+/// Test that plain HTTP is recognised as having no header
+#[test]
+fn test_parse_not_proxy() {
+    assert_eq!(
+        parse_header(b"GET / HTTP/1.1\r\n").unwrap(),
+        ParseOutcome::NotProxy
+    );
+}
+
+/// Test that malformed v1 headers are rejected
+#[test]
+fn test_parse_v1_invalid() {
+    assert!(matches!(
+        parse_header(b"PROXY TCP4 999.0.0.1 10.0.0.1 1 2\r\n"),
+        Err(ProxyProtocolError::Invalid(_))
+    ));
+    assert!(matches!(
+        parse_header(b"PROXY UDP4 1.2.3.4 10.0.0.1 1 2\r\n"),
+        Err(ProxyProtocolError::Invalid(_))
+    ));
+}
+
+/// Test that a v2 PROXY command over IPv4 is parsed
+#[test]
+fn test_parse_v2_inet() {
+    let header = v2_inet_header([198, 51, 100, 9], 6000);
+
+    let outcome = parse_header(&header).unwrap();
+
+    assert_eq!(
+        outcome,
+        ParseOutcome::Header {
+            source: Some("198.51.100.9:6000".parse().unwrap()),
+            len: 28,
+        }
+    );
+}
+
+/// Test that a v2 PROXY command over IPv6 is parsed
+#[test]
+fn test_parse_v2_inet6() {
+    let mut header = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+    header.extend_from_slice(&[0x21, 0x21, 0x00, 36]);
+    header.extend_from_slice(
+        &"2001:db8::7"
+            .parse::<std::net::Ipv6Addr>()
+            .unwrap()
+            .octets(),
+    );
+    header.extend_from_slice(&[0u8; 16]);
+    header.extend_from_slice(&7000u16.to_be_bytes());
+    header.extend_from_slice(&443u16.to_be_bytes());
+
+    let outcome = parse_header(&header).unwrap();
+
+    assert!(matches!(
+        outcome,
+        ParseOutcome::Header { source: Some(addr), len: 52 } if addr == "[2001:db8::7]:7000".parse().unwrap()
+    ));
+}
+
+/// Test that a v2 LOCAL command carries no source address
+#[test]
+fn test_parse_v2_local() {
+    let mut header = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+    header.extend_from_slice(&[0x20, 0x00, 0x00, 0x00]);
+
+    let outcome = parse_header(&header).unwrap();
+
+    assert_eq!(
+        outcome,
+        ParseOutcome::Header {
+            source: None,
+            len: 16
+        }
+    );
+}
+
+/// Serve a router answering with the client address it sees, returning its address
+async fn serve_client_addr(mode: ProxyProtocolMode, max_connections: Option<usize>) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Same wrapping order as main; tap_io gives axum's `Connected` impl for ConnectInfo
+    let listener = ConnectionLimit::new(listener, max_connections);
+    let listener = ProxyProtocolListener::new(listener, mode)
+        .unwrap()
+        .tap_io(|_| {});
+
+    let app = Router::new().route(
+        "/",
+        get(|ConnectInfo(client): ConnectInfo<SocketAddr>| async move { client.ip().to_string() }),
+    );
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .unwrap()
+    });
+
+    addr
+}
+
+/// Send raw bytes and return whatever the server answers before closing
+async fn send_raw(addr: SocketAddr, bytes: &[u8]) -> String {
+    let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    client.write_all(bytes).await.unwrap();
+
+    let mut response = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(2), client.read_to_end(&mut response)).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+/// Test that the v1 header's source address reaches handlers as ConnectInfo
+#[tokio::test]
+async fn test_listener_uses_v1_source_address() {
+    let addr = serve_client_addr(ProxyProtocolMode::Required, None).await;
+
+    let response = send_raw(
+        addr,
+        b"PROXY TCP4 203.0.113.7 10.0.0.1 51234 80\r\nGET / HTTP/1.1\r\nhost: x\r\nconnection: close\r\n\r\n",
+    )
+    .await;
+
+    assert!(response.starts_with("HTTP/1.1 200"), "got: {response}");
+    assert!(response.ends_with("203.0.113.7"), "got: {response}");
+}
+
+/// Test that the v2 header's source address reaches handlers as ConnectInfo
+#[tokio::test]
+async fn test_listener_uses_v2_source_address() {
+    let addr = serve_client_addr(ProxyProtocolMode::Required, None).await;
+
+    let mut bytes = v2_inet_header([198, 51, 100, 9], 6000);
+    bytes.extend_from_slice(b"GET / HTTP/1.1\r\nhost: x\r\nconnection: close\r\n\r\n");
+    let response = send_raw(addr, &bytes).await;
+
+    assert!(response.ends_with("198.51.100.9"), "got: {response}");
+}
+
+/// Test that required mode closes connections without a header
+#[tokio::test]
+async fn test_listener_required_rejects_missing_header() {
+    let addr = serve_client_addr(ProxyProtocolMode::Required, None).await;
+
+    let response = send_raw(
+        addr,
+        b"GET / HTTP/1.1\r\nhost: x\r\nconnection: close\r\n\r\n",
+    )
+    .await;
+
+    assert!(response.is_empty(), "got: {response}");
+}
+
+/// Test that optional mode falls back to the socket peer without a header
+#[tokio::test]
+async fn test_listener_optional_allows_missing_header() {
+    let addr = serve_client_addr(ProxyProtocolMode::Optional, None).await;
+
+    let response = send_raw(
+        addr,
+        b"GET / HTTP/1.1\r\nhost: x\r\nconnection: close\r\n\r\n",
+    )
+    .await;
+
+    assert!(response.ends_with("127.0.0.1"), "got: {response}");
+}
+
+/// Test that connections still sending their header count toward max_connections
+#[tokio::test]
+async fn test_connection_limit_covers_pending_headers() {
+    let addr = serve_client_addr(ProxyProtocolMode::Required, Some(1)).await;
+
+    // Holds the only permit while the gateway waits for its header
+    let stalled = tokio::net::TcpStream::connect(addr).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    client
+        .write_all(b"PROXY TCP4 203.0.113.7 10.0.0.1 51234 80\r\nGET / HTTP/1.1\r\nhost: x\r\nconnection: close\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut response = Vec::new();
+    let blocked = tokio::time::timeout(
+        Duration::from_millis(300),
+        client.read_to_end(&mut response),
+    )
+    .await;
+    assert!(
+        blocked.is_err(),
+        "second connection should wait for a permit"
+    );
+
+    // Closing the stalled connection releases its permit
+    drop(stalled);
+    tokio::time::timeout(Duration::from_secs(2), client.read_to_end(&mut response))
+        .await
+        .expect("second connection should be served once the permit is free")
+        .unwrap();
+
+    let response = String::from_utf8_lossy(&response);
+    assert!(response.ends_with("203.0.113.7"), "got: {response}");
+}
@@ -0,0 +1,113 @@
+use api_gateway::config::{AppConfig, ConfigError};
+use axum::{http::header, routing::get, Router};
+use tokio::net::TcpListener;
+
+/// Write a config file to a unique temp path and return the path
+fn write_config(name: &str, contents: &str) -> String {
+    let path =
+        std::env::temp_dir().join(format!("api-gateway-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+/// Start a mock config service and return its base URL
+async fn start_config_service() -> String {
+    let app = Router::new()
+        .route(
+            "/gateway.toml",
+            get(|| async { "port = 4321\nrequest_timeout_ms = 20000\n" }),
+        )
+        .route(
+            "/gateway",
+            get(|| async {
+                (
+                    [(header::CONTENT_TYPE, "application/json")],
+                    r#"{"port": 4322}"#,
+                )
+            }),
+        );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+/// Get a URL for a port nothing is listening on
+async fn unreachable_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{}/gateway.toml", addr)
+}
+
+/// Test that remote TOML config overrides the local file
+#[tokio::test]
+async fn test_remote_toml_overrides_file() {
+    let base_url = start_config_service().await;
+    let path = write_config(
+        "remote-toml",
+        &format!(
+            "port = 3000\nrequest_timeout_ms = 1000\nremote_config_url = \"{}/gateway.toml\"\n",
+            base_url
+        ),
+    );
+
+    let cfg = AppConfig::load_from_file_with_remote(&path).await.unwrap();
+
+    assert_eq!(cfg.port, 4321);
+    assert_eq!(cfg.request_timeout_ms, 20000);
+}
+
+/// Test that JSON remote config is detected from the content type
+#[tokio::test]
+async fn test_remote_json_detected_by_content_type() {
+    let base_url = start_config_service().await;
+    let path = write_config(
+        "remote-json",
+        &format!(
+            "port = 3000\nremote_config_url = \"{}/gateway\"\n",
+            base_url
+        ),
+    );
+
+    let cfg = AppConfig::load_from_file_with_remote(&path).await.unwrap();
+
+    assert_eq!(cfg.port, 4322);
+}
+
+/// Test that an unreachable optional remote config falls back to local files
+#[tokio::test]
+async fn test_unreachable_optional_remote_falls_back() {
+    let path = write_config(
+        "remote-optional",
+        &format!(
+            "port = 3005\nremote_config_url = \"{}\"\nremote_config_timeout_ms = 500\n",
+            unreachable_url().await
+        ),
+    );
+
+    let cfg = AppConfig::load_from_file_with_remote(&path).await.unwrap();
+
+    assert_eq!(cfg.port, 3005);
+}
+
+/// Test that an unreachable required remote config fails startup
+#[tokio::test]
+async fn test_unreachable_required_remote_fails() {
+    let path = write_config(
+        "remote-required",
+        &format!(
+            "remote_config_url = \"{}\"\nremote_config_required = true\nremote_config_timeout_ms = 500\n",
+            unreachable_url().await
+        ),
+    );
+
+    let result = AppConfig::load_from_file_with_remote(&path).await;
+
+    assert!(
+        matches!(result, Err(ConfigError::RemoteConfigFetch(_, _))),
+        "Expected RemoteConfigFetch error, got: {:?}",
+        result
+    );
+}
@@ -1,6 +1,9 @@
+use api_gateway::{request_id_middleware, RequestIdScheme};
 use axum::{
     body::Body,
     http::{Request, StatusCode},
+    routing::get,
+    Router,
 };
 use tower::ServiceExt;
 use uuid::Uuid;
@@ -161,3 +164,62 @@ async fn test_different_requests_get_different_uuids() {
         "Different requests should get different request IDs"
     );
 }
+
+/// Test that UUIDv7 request IDs sort in generation order
+#[test]
+fn test_uuid_v7_ids_are_monotonic() {
+    let ids: Vec<String> = (0..1000)
+        .map(|_| RequestIdScheme::UuidV7.generate())
+        .collect();
+
+    for id in &ids {
+        let uuid = Uuid::parse_str(id).unwrap();
+        assert_eq!(uuid.get_version_num(), 7, "Should be a UUIDv7: {}", id);
+    }
+
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(sorted, ids, "UUIDv7 IDs should sort in generation order");
+}
+
+/// Test that ULID request IDs are well-formed and sort in generation order
+#[test]
+fn test_ulid_ids_are_well_formed_and_monotonic() {
+    let ids: Vec<String> = (0..1000)
+        .map(|_| RequestIdScheme::Ulid.generate())
+        .collect();
+
+    for id in &ids {
+        assert_eq!(id.len(), 26, "ULID should be 26 characters: {}", id);
+        assert!(
+            id.chars()
+                .all(|c| "0123456789ABCDEFGHJKMNPQRSTVWXYZ".contains(c)),
+            "ULID should use Crockford base32: {}",
+            id
+        );
+    }
+
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(sorted, ids, "ULIDs should sort in generation order");
+}
+
+/// Test that the middleware uses the configured generation scheme
+#[tokio::test]
+async fn test_middleware_uses_configured_scheme() {
+    let app = Router::new().route("/", get(|| async { "ok" })).layer(
+        axum::middleware::from_fn_with_state(RequestIdScheme::UuidV7, request_id_middleware),
+    );
+
+    let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    let request_id = response
+        .headers()
+        .get("x-request-id")
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let uuid = Uuid::parse_str(request_id).unwrap();
+    assert_eq!(uuid.get_version_num(), 7);
+}
@@ -0,0 +1,282 @@
+use api_gateway::{
+    config::AppConfig,
+    router::{build_router_with, read_body_with_timeout},
+};
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tower::ServiceExt;
+
+/// Embedder middleware that tags responses with the tenant resolved from the request ID
+async fn tenant_header(request: Request, next: Next) -> Response {
+    let saw_request_id = request.extensions().get::<String>().is_some();
+    let mut response = next.run(request).await;
+    response.headers_mut().insert(
+        "x-tenant",
+        HeaderValue::from_static(if saw_request_id {
+            "resolved"
+        } else {
+            "missing"
+        }),
+    );
+    response
+}
+
+/// Test that embedder layers are applied inside the built-in middleware stack
+#[tokio::test]
+async fn test_custom_layer_registered_before_serving() {
+    let cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    let app = build_router_with(&cfg, |router| {
+        router.layer(axum::middleware::from_fn(tenant_header))
+    })
+    .unwrap();
+
+    let request = Request::builder()
+        .uri("/healthz")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Custom layer ran and could see the request ID set by the built-in stack
+    assert_eq!(response.headers().get("x-tenant").unwrap(), "resolved");
+
+    // Built-in middleware still applies
+    assert!(response.headers().get("x-request-id").is_some());
+}
+
+/// Send a GET /favicon.ico through a router built from `cfg`
+async fn get_favicon(cfg: &AppConfig) -> Response {
+    let app = build_router_with(cfg, |router| router).unwrap();
+
+    let request = Request::builder()
+        .uri("/favicon.ico")
+        .body(Body::empty())
+        .unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+/// Test that favicon requests get 204 when serve_favicon is on without an icon
+#[tokio::test]
+async fn test_favicon_no_content_by_default() {
+    let mut cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    cfg.serve_favicon = true;
+
+    let response = get_favicon(&cfg).await;
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+/// Test that the configured favicon file is served with an image content type
+#[tokio::test]
+async fn test_favicon_serves_configured_icon() {
+    let path = std::env::temp_dir().join(format!("api-gateway-favicon-{}.png", std::process::id()));
+    std::fs::write(&path, b"\x89PNG fake icon").unwrap();
+
+    let mut cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    cfg.serve_favicon = true;
+    cfg.favicon_path = Some(path.to_string_lossy().into_owned());
+
+    let response = get_favicon(&cfg).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get("content-type").unwrap(), "image/png");
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(&body[..], b"\x89PNG fake icon");
+}
+
+/// Test that /favicon.ico is left unrouted when serve_favicon is off
+#[tokio::test]
+async fn test_favicon_disabled_returns_not_found() {
+    let cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+
+    let response = get_favicon(&cfg).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+/// Test that a stalled request body maps to 408 Request Timeout
+#[tokio::test]
+async fn test_stalled_client_body_returns_408() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    cfg.client_body_timeout_ms = 100;
+    let body_timeout = cfg.client_body_timeout();
+    let app = build_router_with(&cfg, |router| {
+        router.route(
+            "/upload",
+            axum::routing::post(move |request: Request| async move {
+                read_body_with_timeout(request.into_body(), body_timeout, 1024)
+                    .await
+                    .map(|body| body.len().to_string())
+            }),
+        )
+    })
+    .unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    // Promise 10 bytes, send 2, then stall
+    let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    client
+        .write_all(b"POST /upload HTTP/1.1\r\nhost: localhost\r\ncontent-length: 10\r\n\r\nab")
+        .await
+        .unwrap();
+
+    let mut response = vec![0u8; 1024];
+    let read = tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        client.read(&mut response),
+    )
+    .await
+    .expect("gateway should answer a stalled body")
+    .unwrap();
+    let response = String::from_utf8_lossy(&response[..read]);
+
+    assert!(response.starts_with("HTTP/1.1 408"), "got: {response}");
+}
+
+/// Test that a slow handler still maps to 504 Gateway Timeout
+#[tokio::test]
+async fn test_slow_handler_returns_504() {
+    let mut cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    cfg.request_timeout_ms = 50;
+    let app = build_router_with(&cfg, |router| router).unwrap();
+
+    let request = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+}
+
+/// Test route that always panics
+async fn panicking() -> &'static str {
+    panic!("deliberate test panic");
+}
+
+/// Test that a panicking handler returns the 500 envelope with the request ID
+#[tokio::test]
+async fn test_panic_returns_envelope_with_request_id() {
+    let cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    let app = build_router_with(&cfg, |router| {
+        router.route("/panic", axum::routing::get(panicking))
+    })
+    .unwrap();
+
+    let request = Request::builder()
+        .uri("/panic")
+        .header("x-request-id", "panic-req-1")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        response.headers().get("x-request-id").unwrap(),
+        "panic-req-1"
+    );
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["status"], 500);
+    assert_eq!(json["request_id"], "panic-req-1");
+}
+
+/// Test that credentials are allowed and the specific origin is echoed
+#[tokio::test]
+async fn test_cors_allow_credentials_with_specific_origin() {
+    let mut cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    cfg.cors_origins = vec!["https://app.example.com".to_string()];
+    cfg.cors_allow_credentials = true;
+    let app = build_router_with(&cfg, |router| router).unwrap();
+
+    let request = Request::builder()
+        .uri("/")
+        .header("origin", "https://app.example.com")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .unwrap(),
+        "https://app.example.com"
+    );
+    assert_eq!(
+        response
+            .headers()
+            .get("access-control-allow-credentials")
+            .unwrap(),
+        "true"
+    );
+}
+
+/// Test that the credentials header is absent by default
+#[tokio::test]
+async fn test_cors_credentials_off_by_default() {
+    let mut cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    cfg.cors_origins = vec!["https://app.example.com".to_string()];
+    let app = build_router_with(&cfg, |router| router).unwrap();
+
+    let request = Request::builder()
+        .uri("/")
+        .header("origin", "https://app.example.com")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert!(response
+        .headers()
+        .get("access-control-allow-credentials")
+        .is_none());
+}
+
+/// Test that a raw HTTP/1.0 request without Host gets 400 and a closed connection
+#[tokio::test]
+async fn test_http10_without_host_over_the_wire() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let cfg = AppConfig::load_from_file("nonexistent-router-test-config").unwrap();
+    let app = build_router_with(&cfg, |router| router).unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+    client.write_all(b"GET / HTTP/1.0\r\n\r\n").await.unwrap();
+
+    // read_to_end only finishes because the server closes the connection
+    let mut response = Vec::new();
+    tokio::time::timeout(
+        std::time::Duration::from_secs(2),
+        client.read_to_end(&mut response),
+    )
+    .await
+    .expect("server should close the connection")
+    .unwrap();
+    let response = String::from_utf8_lossy(&response);
+
+    assert!(response.contains(" 400 "), "got: {response}");
+}